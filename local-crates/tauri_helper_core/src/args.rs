@@ -0,0 +1,103 @@
+use syn::{Attribute, Ident, LitInt, LitStr, Meta, ext::IdentExt, meta::ParseNestedMeta};
+
+/// Arguments accepted by the `#[auto_collect_command]` attribute.
+///
+/// This is shared between the attribute macro, which validates the arguments, and the scanner,
+/// which records them in the command files.
+#[derive(Debug, Clone, Default)]
+pub struct CommandArgs {
+    /// Platform the command is restricted to, e.g. `#[auto_collect_command(platform = "mobile")]`.
+    pub platform: Option<String>,
+    /// Group the command belongs to, e.g. `#[auto_collect_command(group = "settings")]`.
+    pub group: Option<String>,
+    /// Name the command is exposed under, e.g. `#[auto_collect_command(rename = "hello")]`.
+    pub rename: Option<String>,
+    /// Other names the command is also exposed under, e.g. the old name during a rename with
+    /// `#[auto_collect_command(rename = "hello", alias = "greet")]`. Can be repeated, each alias
+    /// differing from the others and from the name the command is exposed under.
+    pub aliases: Vec<String>,
+    /// Default timeout frontends should use when invoking the command, in milliseconds, e.g.
+    /// `#[auto_collect_command(timeout_ms = 5000)]`. Only recorded, it isn't enforced.
+    pub timeout_ms: Option<u64>,
+}
+
+impl CommandArgs {
+    /// Parses the arguments of an `#[auto_collect_command]` attribute found in the source.
+    pub fn from_attribute(attr: &Attribute) -> syn::Result<Self> {
+        let mut args = Self::default();
+        if let Meta::List(_) = attr.meta {
+            attr.parse_nested_meta(|meta| args.parse_meta(meta))?;
+        }
+        Ok(args)
+    }
+
+    /// Checks the arguments against the function they are written on, rejecting an alias equal to
+    /// the name of the function when it isn't renamed, since it is already exposed under it.
+    pub fn check_function(&self, function: &Ident) -> syn::Result<()> {
+        if self.rename.is_none() && self.aliases.contains(&function.unraw().to_string()) {
+            return Err(syn::Error::new(
+                function.span(),
+                format!(
+                    "`alias = \"{}\"` is the name of the function, the command is already exposed under it",
+                    function.unraw()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses a single `key = value` argument, meant to be used with `syn::meta::parser`.
+    pub fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("platform") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.platform = Some(value.value());
+            Ok(())
+        } else if meta.path.is_ident("group") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.group = Some(value.value());
+            Ok(())
+        } else if meta.path.is_ident("rename") {
+            let value: LitStr = meta.value()?.parse()?;
+            value.parse::<Ident>().map_err(|_| {
+                syn::Error::new(value.span(), "`rename` must be a valid Rust identifier")
+            })?;
+            if self.aliases.contains(&value.value()) {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "`rename` can't be one of the aliases, the command is already exposed under it",
+                ));
+            }
+            self.rename = Some(value.value());
+            Ok(())
+        } else if meta.path.is_ident("alias") {
+            let value: LitStr = meta.value()?.parse()?;
+            value.parse::<Ident>().map_err(|_| {
+                syn::Error::new(value.span(), "`alias` must be a valid Rust identifier")
+            })?;
+            if self.aliases.contains(&value.value()) {
+                return Err(syn::Error::new(value.span(), "duplicate `alias`"));
+            }
+            if self.rename.as_ref() == Some(&value.value()) {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "`alias` can't be the `rename` name, the command is already exposed under it",
+                ));
+            }
+            self.aliases.push(value.value());
+            Ok(())
+        } else if meta.path.is_ident("timeout_ms") {
+            let value: LitInt = meta.value()?.parse()?;
+            let timeout_ms = value
+                .base10_parse::<u64>()
+                .ok()
+                .filter(|timeout_ms| *timeout_ms > 0)
+                .ok_or_else(|| {
+                    syn::Error::new(value.span(), "`timeout_ms` must be a positive integer")
+                })?;
+            self.timeout_ms = Some(timeout_ms);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported auto_collect_command argument"))
+        }
+    }
+}
@@ -0,0 +1,583 @@
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use syn::{Expr, ExprLit, Lit, Meta, Token, punctuated::Punctuated};
+
+/// A command collected by the scanner, as stored in the command files.
+///
+/// Each command occupies one line of a command file: the full path of the function followed by
+/// optional tab-separated `key=value` metadata fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CommandInfo {
+    /// Full path of the function, e.g. `my_crate::greet`.
+    pub path: String,
+    /// Platform the command is restricted to, `None` means it is available on every platform.
+    pub platform: Option<String>,
+    /// Group the command belongs to, `None` if it isn't part of any group.
+    pub group: Option<String>,
+    /// Name the command is exposed under, `None` means the name of the function.
+    pub name: Option<String>,
+    /// Other names the command is also registered under.
+    pub aliases: Vec<String>,
+    /// Path of the module defining the function, e.g. `commands::users`, `None` for the crate root.
+    pub module: Option<String>,
+    /// Whether the function can't be reached from outside of its crate. Its path then assumes it is
+    /// re-exported from the crate root, while the crate itself reaches it through `module`.
+    pub unreachable: bool,
+    /// `#[cfg(...)]` predicates guarding the command, on the function or one of its modules,
+    /// e.g. `feature = "admin"`.
+    pub cfg: Vec<String>,
+    /// Whether the command is only recorded for the generated metadata and left out of the handlers,
+    /// see `TauriHelperOptions::metadata_only_crates`.
+    pub metadata_only: bool,
+    /// Whether the command takes no arguments from the frontend, only the ones injected by Tauri
+    /// such as `State` or `AppHandle`. Only recorded with `TauriHelperOptions::tag_no_args`.
+    pub no_args: bool,
+    /// Default timeout frontends should use when invoking the command, in milliseconds.
+    pub timeout_ms: Option<u64>,
+    /// Signature of the function as written, e.g. `fn greet(name: String) -> String`, `None` when
+    /// unknown, e.g. with `TauriHelperOptions::fast_scan`. Not part of the JSON outputs.
+    pub signature: Option<String>,
+}
+
+impl CommandInfo {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the name of the function, which is the last segment of its path.
+    pub fn fn_name(&self) -> &str {
+        self.path.rsplit("::").next().unwrap_or(&self.path)
+    }
+
+    /// Returns the name the command is registered under, raw identifiers such as `r#match` are
+    /// registered without their `r#` prefix.
+    pub fn exposed_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .unwrap_or_else(|| self.fn_name().trim_start_matches("r#"))
+    }
+
+    /// Returns the path of the function from the root of its crate, e.g. `users::greet` for
+    /// `my_crate::users::greet`.
+    ///
+    /// The path of a function unreachable from outside of its crate goes through the module
+    /// defining it instead, e.g. `users::greet` for `my_crate::greet` defined in a private `users`.
+    pub fn path_in_crate(&self) -> String {
+        let path = self
+            .path
+            .split_once("::")
+            .map_or(self.path.as_str(), |(_, path)| path);
+        match &self.module {
+            Some(module) if self.unreachable => format!("{}::{}", module, path),
+            _ => path.to_string(),
+        }
+    }
+
+    /// Returns `true` if the command should be registered for the given platform.
+    pub fn matches_platform(&self, platform: &str) -> bool {
+        self.platform.as_deref().is_none_or(|p| p == platform)
+    }
+
+    /// Returns the features that must be enabled for the command to exist, read from its `cfg`
+    /// predicates. Features only appearing under `not(...)` are not included.
+    pub fn features(&self) -> BTreeSet<String> {
+        fn collect(meta: &Meta, features: &mut BTreeSet<String>) {
+            match meta {
+                Meta::NameValue(nv) if nv.path.is_ident("feature") => {
+                    if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(value),
+                        ..
+                    }) = &nv.value
+                    {
+                        features.insert(value.value());
+                    }
+                }
+                Meta::List(list) if list.path.is_ident("all") || list.path.is_ident("any") => {
+                    if let Ok(metas) =
+                        list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    {
+                        metas.iter().for_each(|meta| collect(meta, features));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut features = BTreeSet::new();
+        for cfg in &self.cfg {
+            if let Ok(meta) = syn::parse_str::<Meta>(cfg) {
+                collect(&meta, &mut features);
+            }
+        }
+        features
+    }
+
+    /// Returns the command as written in the JSON outputs, absent metadata is omitted.
+    pub fn to_json(&self) -> Value {
+        let mut entries = Map::new();
+        entries.insert("path".into(), self.path.as_str().into());
+        entries.insert("name".into(), self.exposed_name().into());
+        if let Some(platform) = &self.platform {
+            entries.insert("platform".into(), platform.as_str().into());
+        }
+        if let Some(group) = &self.group {
+            entries.insert("group".into(), group.as_str().into());
+        }
+        if let Some(module) = &self.module {
+            entries.insert("module".into(), module.as_str().into());
+        }
+        if self.unreachable {
+            entries.insert("unreachable".into(), true.into());
+        }
+        if !self.aliases.is_empty() {
+            entries.insert("aliases".into(), self.aliases.clone().into());
+        }
+        if !self.cfg.is_empty() {
+            entries.insert("cfg".into(), self.cfg.clone().into());
+        }
+        if self.metadata_only {
+            entries.insert("metadata_only".into(), true.into());
+        }
+        if self.no_args {
+            entries.insert("no_args".into(), true.into());
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            entries.insert("timeout_ms".into(), timeout_ms.into());
+        }
+        Value::Object(entries)
+    }
+
+    /// Reads a command as written by [`to_json`](Self::to_json), returns `None` if it has no path.
+    ///
+    /// Unknown keys are ignored, like unknown fields of the command files.
+    pub fn from_json(json: &Value) -> Option<Self> {
+        let string = |key| json.get(key).and_then(Value::as_str).map(str::to_string);
+        let strings = |key| {
+            json.get(key)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        };
+        let flag = |key| json.get(key).and_then(Value::as_bool).unwrap_or(false);
+
+        let mut info = Self::new(string("path")?);
+        // the name is always written, it is only a rename if it differs from the function
+        info.name = string("name").filter(|name| name != info.exposed_name());
+        info.platform = string("platform");
+        info.group = string("group");
+        info.module = string("module");
+        info.unreachable = flag("unreachable");
+        info.aliases = strings("aliases");
+        info.cfg = strings("cfg");
+        info.metadata_only = flag("metadata_only");
+        info.no_args = flag("no_args");
+        info.timeout_ms = json.get("timeout_ms").and_then(Value::as_u64);
+        Some(info)
+    }
+
+    /// Serializes the command into a single command file line.
+    pub fn to_line(&self) -> String {
+        let mut line = self.path.clone();
+        if let Some(platform) = &self.platform {
+            push_field(&mut line, "platform", platform);
+        }
+        if let Some(group) = &self.group {
+            push_field(&mut line, "group", group);
+        }
+        if let Some(name) = &self.name {
+            push_field(&mut line, "name", name);
+        }
+        for alias in &self.aliases {
+            push_field(&mut line, "alias", alias);
+        }
+        if let Some(module) = &self.module {
+            push_field(&mut line, "module", module);
+        }
+        if self.unreachable {
+            push_field(&mut line, "unreachable", "true");
+        }
+        for cfg in &self.cfg {
+            push_field(&mut line, "cfg", cfg);
+        }
+        if self.metadata_only {
+            push_field(&mut line, "metadata_only", "true");
+        }
+        if self.no_args {
+            push_field(&mut line, "no_args", "true");
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            push_field(&mut line, "timeout_ms", &timeout_ms.to_string());
+        }
+        if let Some(signature) = &self.signature {
+            push_field(&mut line, "signature", signature);
+        }
+        line
+    }
+
+    /// Parses a command file line, returns `None` for blank lines and `#` comments.
+    ///
+    /// Unknown metadata fields are ignored so that older macros can read newer command files.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let mut fields = line.split('\t');
+        let path = fields.next().filter(|p| !p.is_empty())?;
+        let mut info = Self::new(path.trim());
+
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            let value = unescape(value);
+            match key {
+                "platform" => info.platform = Some(value),
+                "group" => info.group = Some(value),
+                "name" => info.name = Some(value),
+                "alias" => info.aliases.push(value),
+                "module" => info.module = Some(value),
+                "unreachable" => info.unreachable = value == "true",
+                "cfg" => info.cfg.push(value),
+                "metadata_only" => info.metadata_only = value == "true",
+                "no_args" => info.no_args = value == "true",
+                "timeout_ms" => info.timeout_ms = value.parse().ok(),
+                "signature" => info.signature = Some(value),
+                _ => {}
+            }
+        }
+
+        Some(info)
+    }
+}
+
+fn push_field(line: &mut String, key: &str, value: &str) {
+    line.push('\t');
+    line.push_str(key);
+    line.push('=');
+    line.push_str(&escape(value));
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Reads a command file, `None` if it can't be read.
+pub fn read_command_file(path: &Path) -> Option<Vec<CommandInfo>> {
+    let content = fs::read_to_string(path).ok()?;
+    Some(content.lines().filter_map(CommandInfo::from_line).collect())
+}
+
+/// Returns the `.txt` command files of the given directory.
+pub fn command_files(commands_dir: &Path) -> Option<Vec<PathBuf>> {
+    let entries = fs::read_dir(commands_dir).ok()?;
+    Some(
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("txt")
+            })
+            .collect(),
+    )
+}
+
+/// A command file and the commands read from it.
+#[derive(Debug, Clone)]
+pub struct CommandFile {
+    pub path: PathBuf,
+    pub commands: Vec<CommandInfo>,
+}
+
+/// Reads every `.txt` command file of the given directory, keeping track of the file each
+/// command comes from.
+pub fn discover_command_files(commands_dir: &Path) -> Option<Vec<CommandFile>> {
+    let mut files: Vec<CommandFile> = command_files(commands_dir)?
+        .into_iter()
+        .filter_map(|path| {
+            let commands = read_command_file(&path)?;
+            Some(CommandFile { path, commands })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Some(files)
+}
+
+/// Reads every `.txt` command file of the given directory.
+pub fn discover_commands(commands_dir: &Path) -> Option<Vec<CommandInfo>> {
+    let commands = discover_command_files(commands_dir)?
+        .into_iter()
+        .flat_map(|file| file.commands)
+        .collect();
+
+    Some(commands)
+}
+
+/// Error returned when the crate invoking the macros is neither the workspace package nor one of
+/// the crates commands were collected from, which usually means its prefix won't be stripped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCallingCrate {
+    pub calling_crate: String,
+    /// Crates the macros know about, sorted.
+    pub known: Vec<String>,
+}
+
+impl fmt::Display for UnknownCallingCrate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "calling crate `{}` is not a known crate ({}), command prefixes may not be stripped. Check that the package name matches the workspace package.",
+            self.calling_crate,
+            self.known.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownCallingCrate {}
+
+/// Checks that the calling crate is the workspace package or one of the crates of the commands.
+pub fn check_calling_crate(
+    crate_name: &str,
+    calling_crate: &str,
+    commands: &[CommandInfo],
+) -> Result<(), UnknownCallingCrate> {
+    let calling_crate = calling_crate.replace('-', "_");
+    let mut known: Vec<String> = commands
+        .iter()
+        .filter_map(|command| {
+            command
+                .path
+                .split_once("::")
+                .map(|(krate, _)| krate.to_string())
+        })
+        .chain(std::iter::once(crate_name.replace('-', "_")))
+        .collect();
+    known.sort();
+    known.dedup();
+
+    if known.contains(&calling_crate) {
+        Ok(())
+    } else {
+        Err(UnknownCallingCrate {
+            calling_crate,
+            known,
+        })
+    }
+}
+
+/// Replaces the crate prefix of the commands belonging to the calling crate with `crate::` and
+/// validates every path.
+///
+/// `crate_name` is the name of the workspace package, its commands are referred to by their full
+/// path from the crate root when the macros are invoked from it, e.g. `crate::users::greet`, so
+/// that the paths resolve the same from any module, the ones generated by the macros included.
+///
+/// An unknown calling crate isn't reported, callers check it with [`check_calling_crate`] and
+/// report it the way their context allows, e.g. as a `cargo:warning` from a build script.
+///
+/// Raw identifiers are kept as written, a command of `mod r#move` being referred to as
+/// `crate::r#move::cmd`, since the path wouldn't compile without the prefix.
+///
+/// # Panics
+///
+/// Panics if a command path contains characters that are not allowed in a Rust path.
+pub fn normalize_commands(
+    crate_name: &str,
+    calling_crate: &str,
+    commands: Vec<CommandInfo>,
+) -> BTreeSet<CommandInfo> {
+    let crate_name = crate_name.replace('-', "_");
+    let prefix = format!("{}::", crate_name);
+    let is_calling_crate = crate_name == calling_crate.replace('-', "_");
+
+    commands
+        .into_iter()
+        .map(|mut command| {
+            // Rewrite the prefix ONLY if it's the calling crate
+            // unlike other crates, the calling crate can reach the function through its module
+            // without it being re-exported from the crate root
+            if is_calling_crate && command.path.starts_with(&prefix) {
+                command.path = format!("crate::{}", command.path_in_crate());
+            }
+
+            if !command.path.split("::").all(is_path_segment) {
+                panic!("Invalid function name `{}` in command file", command.path);
+            }
+
+            command
+        })
+        .collect()
+}
+
+/// Returns `true` if `path` is a full command path such as `my_crate::users::greet`.
+pub fn is_command_path(path: &str) -> bool {
+    path.contains("::") && path.split("::").all(is_path_segment)
+}
+
+/// Returns `true` for a path segment made of ASCII alphanumerics and underscores, optionally
+/// prefixed with `r#` for raw identifiers.
+fn is_path_segment(segment: &str) -> bool {
+    let name = segment.strip_prefix("r#").unwrap_or(segment);
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(path: &str, module: Option<&str>, unreachable: bool) -> CommandInfo {
+        CommandInfo {
+            module: module.map(str::to_string),
+            unreachable,
+            ..CommandInfo::new(path)
+        }
+    }
+
+    #[test]
+    fn path_in_crate_keeps_the_public_path() {
+        let nested = command(
+            "app::commands::users::greet",
+            Some("commands::users"),
+            false,
+        );
+        assert_eq!(nested.path_in_crate(), "commands::users::greet");
+
+        // re-exported from the crate root out of a private module
+        let reexported = command("app::greet", Some("commands::users"), false);
+        assert_eq!(reexported.path_in_crate(), "greet");
+    }
+
+    #[test]
+    fn path_in_crate_goes_through_the_module_of_unreachable_functions() {
+        let private = command("app::greet", Some("commands::users"), true);
+        assert_eq!(private.path_in_crate(), "commands::users::greet");
+    }
+
+    #[test]
+    fn normalize_commands_refers_to_nested_commands_from_the_crate_root() {
+        let commands = vec![
+            command(
+                "app::commands::users::greet",
+                Some("commands::users"),
+                false,
+            ),
+            command("other::commands::ping", Some("commands"), false),
+        ];
+        let paths: Vec<String> = normalize_commands("app", "app", commands)
+            .into_iter()
+            .map(|command| command.path)
+            .collect();
+        assert_eq!(
+            paths,
+            ["crate::commands::users::greet", "other::commands::ping"]
+        );
+    }
+
+    #[test]
+    fn unreachable_round_trips_through_the_command_files() {
+        let private = command("app::greet", Some("commands::users"), true);
+        assert_eq!(
+            CommandInfo::from_line(&private.to_line()),
+            Some(private.clone())
+        );
+        assert_eq!(CommandInfo::from_json(&private.to_json()), Some(private));
+    }
+
+    #[test]
+    fn unknown_calling_crate_is_reported() {
+        let commands = [
+            CommandInfo::new("app::greet"),
+            CommandInfo::new("users::load"),
+        ];
+
+        assert_eq!(check_calling_crate("app", "users", &commands), Ok(()));
+        assert_eq!(check_calling_crate("my-app", "my_app", &[]), Ok(()));
+        assert_eq!(
+            check_calling_crate("app", "renamed-app", &commands),
+            Err(UnknownCallingCrate {
+                calling_crate: "renamed_app".to_string(),
+                known: vec!["app".to_string(), "users".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn raw_identifiers_are_valid_paths() {
+        let commands = normalize_commands(
+            "app",
+            "app",
+            vec![
+                CommandInfo::new("app::r#match"),
+                CommandInfo::new("users::r#type::r#move"),
+            ],
+        );
+
+        let paths: Vec<&str> = commands
+            .iter()
+            .map(|command| command.path.as_str())
+            .collect();
+        assert_eq!(paths, ["crate::r#match", "users::r#type::r#move"]);
+        for path in paths {
+            assert!(syn::parse_str::<syn::Path>(path).is_ok(), "{}", path);
+        }
+        assert!(is_command_path("app::r#match"));
+        assert!(!is_command_path("app::r#"));
+        assert!(!is_command_path("app::#match"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid function name `crate::greet-user` in command file")]
+    fn invalid_paths_are_rejected() {
+        normalize_commands("app", "app", vec![CommandInfo::new("app::greet-user")]);
+    }
+
+    #[test]
+    fn comment_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("app.txt"),
+            "# generated by tauri-helper for crate app\napp::greet\n\n  # app::disabled\napp::load_user\n",
+        )
+        .unwrap();
+
+        let paths: Vec<String> = discover_commands(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|command| command.path)
+            .collect();
+        assert_eq!(paths, ["app::greet", "app::load_user"]);
+    }
+}
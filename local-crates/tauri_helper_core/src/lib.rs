@@ -1,42 +1,214 @@
+pub mod args;
+pub mod command;
+pub mod macro_support;
 pub mod types;
 use std::{
-    env, fs,
+    env,
+    error::Error,
+    fmt, fs,
     path::{Path, PathBuf},
 };
-use types::CargoToml;
+use types::{CargoToml, MemberManifest};
 
+/// Finds the root of the workspace `start_dir` belongs to.
+///
+/// The returned path is canonical, so that the build script writing the command files and the
+/// macros reading them agree on their location even when the build runs through a symlink.
+///
+/// Apps made of a single crate, typically `src-tauri`, have no workspace: the directory of the
+/// closest package is returned instead, see [`try_get_workspace_members`].
 pub fn find_workspace_dir(start_dir: &Path) -> PathBuf {
-    let mut current_dir = start_dir.to_path_buf();
+    let mut current_dir = start_dir
+        .canonicalize()
+        .unwrap_or_else(|_| start_dir.to_path_buf());
+    let mut package_dir = None;
     loop {
         if current_dir.join("Cargo.toml").exists()
             && let Ok(contents) = fs::read_to_string(current_dir.join("Cargo.toml"))
-            && contents.contains("[workspace]")
         {
-            return current_dir;
+            if declares_workspace(&contents) {
+                return current_dir;
+            }
+            package_dir.get_or_insert_with(|| current_dir.clone());
         }
         if !current_dir.pop() {
-            panic!("Workspace root not found from {}", start_dir.display());
+            return package_dir.unwrap_or_else(|| {
+                panic!("Workspace root not found from {}", start_dir.display())
+            });
         }
     }
 }
 
+/// Finds the root of the workspace of the crate being built, from `CARGO_MANIFEST_DIR`.
+///
+/// Outside of Cargo, when the variable isn't set, the workspace is searched from the current
+/// directory instead.
+pub fn current_workspace_dir() -> PathBuf {
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => find_workspace_dir(Path::new(&manifest_dir)),
+        None => find_workspace_dir(&env::current_dir().unwrap()),
+    }
+}
+
+/// Returns `true` if the manifest declares a workspace.
+///
+/// `[workspace.metadata.*]` tables alone, as written by some tools, implicitly define a `workspace`
+/// table without making the package a workspace root. An empty `[workspace]` header does, which
+/// the parsed table can't tell apart, so the headers are looked at as well.
+fn declares_workspace(contents: &str) -> bool {
+    let has_header = contents.lines().any(|line| {
+        let line = line.split('#').next().unwrap_or_default();
+        line.trim()
+            .strip_prefix('[')
+            .and_then(|l| l.strip_suffix(']'))
+            .map(str::trim)
+            == Some("workspace")
+    });
+    match toml::from_str::<toml::Table>(contents) {
+        Ok(manifest) => match manifest.get("workspace") {
+            Some(toml::Value::Table(workspace)) => {
+                has_header || workspace.keys().any(|key| key != "metadata")
+            }
+            _ => false,
+        },
+        // unparsable manifests make Cargo fail anyway, the headers are the best guess
+        Err(_) => has_header,
+    }
+}
+
+/// Returns the directory holding the command files of the workspace the current crate belongs to.
+pub fn get_commands_dir() -> PathBuf {
+    let workspace_root = current_workspace_dir();
+    commands_dir_at(&workspace_root)
+}
+
+/// Returns the directory holding the command files of the workspace located in `workspace_root`.
+///
+/// This is `target/tauri_commands_list` unless `TAURI_HELPER_COMMANDS_DIR` is set to a path
+/// relative to the workspace root, e.g. a committed `.tauri-helper/commands` directory. Set it in
+/// the `[env]` section of `.cargo/config.toml` so that the build script and the macros agree.
+pub fn commands_dir_at(workspace_root: &Path) -> PathBuf {
+    match env::var_os("TAURI_HELPER_COMMANDS_DIR") {
+        Some(dir) => workspace_root.join(dir),
+        None => workspace_root.join("target").join("tauri_commands_list"),
+    }
+}
+
+/// Returns `path` relative to `workspace_root` with `/` separators, so that the paths recorded in
+/// the generated outputs are the same on every machine. Paths outside of the workspace are
+/// returned as they are.
+pub fn workspace_relative(workspace_root: &Path, path: &Path) -> String {
+    match path.strip_prefix(workspace_root) {
+        Ok(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.display().to_string(),
+    }
+}
+
 pub fn get_workspace_members(workspace_root: &Path) -> Vec<String> {
+    try_get_workspace_members(workspace_root).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Reads the `members` of the `[workspace]` section of the manifest located in `workspace_root`.
+///
+/// Only that key is read, so that manifests that don't match what the rest of the crate expects
+/// (virtual manifests, unusual layouts, ...) still give their members. A `[workspace]` without
+/// `members` has none, and a package without a `[workspace]` section is a single-crate app whose
+/// only member is the package itself, `"."`. The leading `./` and trailing `/` of the members
+/// are removed, `./crates/foo/` being read as `crates/foo`.
+pub fn try_get_workspace_members(workspace_root: &Path) -> Result<Vec<String>, WorkspaceError> {
     let cargo_toml = workspace_root.join("Cargo.toml");
-    let contents = fs::read_to_string(&cargo_toml).unwrap_or_else(|_| {
-        panic!(
-            "Failed to read workspace Cargo.toml at {}",
-            cargo_toml.display()
-        );
-    });
+    let error = |reason: String| WorkspaceError {
+        manifest: cargo_toml.clone(),
+        reason,
+    };
+
+    let contents = fs::read_to_string(&cargo_toml).map_err(|e| error(e.to_string()))?;
+    let manifest: toml::Table = toml::from_str(&contents).map_err(|e| error(e.to_string()))?;
+
+    let workspace = match manifest.get("workspace") {
+        Some(toml::Value::Table(_)) | None
+            if !declares_workspace(&contents) && manifest.contains_key("package") =>
+        {
+            return Ok(vec![".".to_string()]);
+        }
+        Some(toml::Value::Table(workspace)) => workspace,
+        Some(_) => return Err(error("`workspace` is not a table".to_string())),
+        None => return Err(error("there is no `[workspace]` section".to_string())),
+    };
+
+    match workspace.get("members") {
+        None => Ok(Vec::new()),
+        Some(toml::Value::Array(members)) => members
+            .iter()
+            .map(|member| match member {
+                toml::Value::String(member) => Ok(normalize_member(member)),
+                other => Err(error(format!(
+                    "`workspace.members` must only contain strings, found `{}`",
+                    other
+                ))),
+            })
+            .collect(),
+        Some(other) => Err(error(format!(
+            "`workspace.members` must be an array of strings, found `{}`",
+            other
+        ))),
+    }
+}
 
-    let toml_content: CargoToml = toml::from_str(&contents).unwrap();
+/// Removes the leading `./` and trailing `/` of a member so that it gives the same paths as its
+/// usual form, the workspace root itself, `./` included, becoming `"."`.
+fn normalize_member(member: &str) -> String {
+    let mut member = member.trim();
+    while let Some(rest) = member.strip_prefix("./") {
+        member = rest.trim_start_matches('/');
+    }
+    let member = member.trim_end_matches('/');
+    if member.is_empty() {
+        ".".to_string()
+    } else {
+        member.to_string()
+    }
+}
 
-    toml_content.workspace.members
+/// Error returned when the members of a workspace can't be read from its manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceError {
+    pub manifest: PathBuf,
+    pub reason: String,
 }
 
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to read the workspace members from {}: {}",
+            self.manifest.display(),
+            self.reason
+        )
+    }
+}
+
+impl Error for WorkspaceError {}
+
 pub fn get_workspace() -> CargoToml {
-    let workspace_root = find_workspace_dir(Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()));
+    let workspace_root = current_workspace_dir();
+    get_workspace_at(&workspace_root)
+}
+
+/// Same as [`get_workspace`] for the workspace containing the current directory.
+///
+/// Tools such as an `xtask` crate have their own `CARGO_MANIFEST_DIR`, which may not belong to the
+/// workspace of the app, running them from the app's directory finds the right one.
+pub fn get_workspace_from_cwd() -> CargoToml {
+    get_workspace_at(&find_workspace_dir(&env::current_dir().unwrap()))
+}
 
+/// Reads the manifest of the workspace located in `workspace_root`.
+pub fn get_workspace_at(workspace_root: &Path) -> CargoToml {
     let cargo_toml = workspace_root.join("Cargo.toml");
     let toml_contents = fs::read_to_string(&cargo_toml).unwrap_or_else(|_| {
         panic!(
@@ -53,3 +225,160 @@ pub fn get_workspace_pkg_name() -> String {
     let cont = get_workspace();
     cont.package.name
 }
+
+/// Reads the targets of the member located in `manifest_dir`, missing or unreadable manifests
+/// fall back to Cargo's default layout.
+pub fn get_member_manifest(manifest_dir: &Path) -> MemberManifest {
+    fs::read_to_string(manifest_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads the members of a workspace whose manifest is `manifest`.
+    fn members(manifest: &str) -> Result<Vec<String>, WorkspaceError> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+        try_get_workspace_members(dir.path()).map_err(|e| WorkspaceError {
+            manifest: PathBuf::from("Cargo.toml"),
+            ..e
+        })
+    }
+
+    #[test]
+    fn members_of_unusual_but_valid_manifests() {
+        // a dotted key and members written as paths
+        assert_eq!(
+            members(
+                "workspace.members = [\"./crates/core/\", \"./\"]\nworkspace.resolver = \"2\"\n"
+            ),
+            Ok(vec!["crates/core".to_string(), ".".to_string()])
+        );
+        // an inline table, with keys the rest of the crate doesn't know
+        assert_eq!(
+            members(
+                "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"cmds\"]\nmetadata = { custom = { nested = [1, 2] } }\n"
+            ),
+            Ok(vec!["cmds".to_string()])
+        );
+        assert_eq!(members("[workspace]\nresolver = \"2\"\n"), Ok(Vec::new()));
+        assert_eq!(
+            members("[package]\nname = \"app\"\n"),
+            Ok(vec![".".to_string()])
+        );
+    }
+
+    #[test]
+    fn members_of_an_odd_structure_are_an_error() {
+        let error = |reason: &str| {
+            Err(WorkspaceError {
+                manifest: PathBuf::from("Cargo.toml"),
+                reason: reason.to_string(),
+            })
+        };
+
+        assert_eq!(
+            members("[workspace]\nmembers = \"cmds\"\n"),
+            error("`workspace.members` must be an array of strings, found `\"cmds\"`")
+        );
+        assert_eq!(
+            members("[workspace]\nmembers = [\"cmds\", { path = \"users\" }]\n"),
+            error("`workspace.members` must only contain strings, found `{ path = \"users\" }`")
+        );
+        assert_eq!(
+            members("workspace = \"all\"\n"),
+            error("`workspace` is not a table")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn workspace_found_through_a_symlink_is_canonical() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        fs::create_dir_all(workspace.join("cmds/src")).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"cmds\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("cmds/Cargo.toml"),
+            "[package]\nname = \"cmds\"\n",
+        )
+        .unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&workspace, &link).unwrap();
+
+        let canonical = workspace.canonicalize().unwrap();
+        assert_eq!(find_workspace_dir(&link.join("cmds")), canonical);
+        assert_eq!(find_workspace_dir(&workspace.join("cmds/src")), canonical);
+        // the build script and the macros agree on the commands directory
+        assert_eq!(
+            commands_dir_at(&find_workspace_dir(&link.join("cmds"))),
+            commands_dir_at(&find_workspace_dir(&workspace.join("cmds")))
+        );
+    }
+
+    #[test]
+    fn workspace_roots_are_detected_from_their_structure() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let write = |path: &str, contents: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        // tab-indented, with a trailing comment
+        write(
+            "Cargo.toml",
+            "\t[workspace]  # the app and its plugins\n\tmembers = [\"app\", \"plugin\"]\n",
+        );
+        // metadata sub-tables and a header in a string don't make a workspace
+        write(
+            "app/Cargo.toml",
+            "[package]\nname = \"app\"\ndescription = \"see [workspace]\"\n\n[workspace.metadata.tauri]\nbundle = true\n",
+        );
+        write("plugin/Cargo.toml", "[package]\nname = \"plugin\"\n");
+
+        assert_eq!(find_workspace_dir(&root.join("app")), root);
+        assert_eq!(find_workspace_dir(&root.join("plugin")), root);
+        assert_eq!(
+            try_get_workspace_members(&root),
+            Ok(vec!["app".to_string(), "plugin".to_string()])
+        );
+        // on its own, the app is a single-crate app
+        assert_eq!(
+            try_get_workspace_members(&root.join("app")),
+            Ok(vec![".".to_string()])
+        );
+
+        // an empty `[workspace]` still makes a workspace root
+        write(
+            "app/Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\n",
+        );
+        assert_eq!(find_workspace_dir(&root.join("app")), root.join("app"));
+    }
+
+    #[test]
+    fn members_are_normalized() {
+        assert_eq!(
+            members(
+                "[workspace]\nmembers = [\"./crates/users/\", \"crates/settings//\", \"././admin\", \"./\", \".\", \"src-tauri\"]\n"
+            ),
+            Ok(vec![
+                "crates/users".to_string(),
+                "crates/settings".to_string(),
+                "admin".to_string(),
+                ".".to_string(),
+                ".".to_string(),
+                "src-tauri".to_string(),
+            ])
+        );
+    }
+}
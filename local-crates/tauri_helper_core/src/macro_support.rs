@@ -0,0 +1,298 @@
+//! Building blocks for procedural macros that want to reuse the command discovery of
+//! tauri-helper, e.g. to build their own handler invocations.
+//!
+//! These functions are meant to be called while a macro expands, they read the command files
+//! written by `tauri_helper::generate_command_file` in the build script.
+
+use serde_json::{Value, json};
+use std::{collections::BTreeSet, env, fs, path::Path};
+
+use crate::command::{
+    CommandFile, CommandInfo, check_calling_crate, discover_command_files, normalize_commands,
+};
+use crate::{commands_dir_at, current_workspace_dir, get_workspace_pkg_name, workspace_relative};
+
+/// Returns the crate the macros are being expanded in, the package being compiled, or the
+/// workspace package when it is unknown.
+///
+/// Every collection macro determines the calling crate through this function so that they all
+/// strip the same prefixes and agree on the identity of the commands.
+pub fn calling_crate() -> String {
+    env::var("CARGO_PKG_NAME").unwrap_or_else(|_| get_workspace_pkg_name())
+}
+
+/// Collects all Tauri commands from the workspace's command files, `calling_crate` being the
+/// crate whose prefix is stripped from the paths, usually [`calling_crate`].
+///
+/// Commands of the metadata-only crates are left out since they must not be registered. The set is
+/// ordered by path, every macro iterating it in that order so that their expansions are the same
+/// from one build to the next, whatever the order of the command files.
+///
+/// When `TAURI_HELPER_VERBOSE` is set, a note listing every command with the command file it was
+/// read from is printed at compile time.
+pub fn collect_commands(calling_crate: String) -> BTreeSet<CommandInfo> {
+    let workspace_package = get_workspace_pkg_name();
+    let commands = collect_commands_with_full_paths();
+    if let Err(e) = check_calling_crate(&workspace_package, &calling_crate, &commands) {
+        eprintln!("Warning: {}", e);
+    }
+    normalize_commands(&workspace_package, &calling_crate, commands)
+}
+
+/// Collects all Tauri commands from the workspace's command files like [`collect_commands`],
+/// keeping the full path of every command, e.g. to refer to them from a generated module.
+///
+/// When `TAURI_HELPER_MACRO_DEBUG` is set, a `tauri_helper_macro_debug.json` file describing the
+/// directory read, the command files found and the commands collected or dropped is written into
+/// the commands directory, see [`debug_report`].
+pub fn collect_commands_with_full_paths() -> Vec<CommandInfo> {
+    let workspace_root = current_workspace_dir();
+    let commands_dir = commands_dir_at(&workspace_root);
+    let files = discover_command_files(&commands_dir);
+
+    if env::var_os("TAURI_HELPER_MACRO_DEBUG").is_some() {
+        let report = debug_report(&workspace_root, &commands_dir, files.as_deref());
+        let written = fs::create_dir_all(&commands_dir).and_then(|_| {
+            fs::write(
+                commands_dir.join("tauri_helper_macro_debug.json"),
+                serde_json::to_string_pretty(&report).unwrap() + "\n",
+            )
+        });
+        if let Err(e) = written {
+            eprintln!("Warning: Failed to write the macro debug file: {}", e);
+        }
+    }
+
+    match files {
+        Some(files) => {
+            if env::var_os("TAURI_HELPER_VERBOSE").is_some() {
+                eprintln!("{}", attribution_note(&files));
+            }
+            files
+                .into_iter()
+                .flat_map(|file| file.commands)
+                .filter(|command| !command.metadata_only)
+                .collect()
+        }
+        None => {
+            eprintln!(
+                "Warning: No commands directory found at {}",
+                commands_dir.display()
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Describes what the collection macros discover, for debugging builds where their state can't
+/// be inspected otherwise:
+///
+/// ```json
+/// {
+///   "crate": "my_app",
+///   "commands_dir": "target/tauri_commands_list",
+///   "commands_dir_found": true,
+///   "files": [{ "path": "target/tauri_commands_list/my_app.txt", "commands": 2 }],
+///   "collected": [{ "path": "my_app::greet", "name": "greet" }],
+///   "dropped": [{ "path": "shared::ping", "reason": "metadata_only" }]
+/// }
+/// ```
+///
+/// `files` is `None` when the commands directory doesn't exist. Paths are relative to
+/// `workspace_root`, see [`workspace_relative`].
+pub fn debug_report(
+    workspace_root: &Path,
+    commands_dir: &Path,
+    files: Option<&[CommandFile]>,
+) -> Value {
+    let files = files.unwrap_or_default();
+    let (dropped, collected): (Vec<&CommandInfo>, Vec<&CommandInfo>) = files
+        .iter()
+        .flat_map(|file| &file.commands)
+        .partition(|command| command.metadata_only);
+
+    json!({
+        "crate": env::var("CARGO_CRATE_NAME").unwrap_or_default(),
+        "commands_dir": workspace_relative(workspace_root, commands_dir),
+        "commands_dir_found": commands_dir.is_dir(),
+        "files": files
+            .iter()
+            .map(|file| {
+                json!({
+                    "path": workspace_relative(workspace_root, &file.path),
+                    "commands": file.commands.len(),
+                })
+            })
+            .collect::<Vec<_>>(),
+        "collected": collected.into_iter().map(CommandInfo::to_json).collect::<Vec<_>>(),
+        "dropped": dropped
+            .into_iter()
+            .map(|command| json!({ "path": command.path, "reason": "metadata_only" }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Lists the commands of each command file, one per line.
+fn attribution_note(files: &[CommandFile]) -> String {
+    let mut note = String::from("Note: tauri-helper collected the following commands:");
+    for file in files {
+        for command in &file.commands {
+            note.push_str(&format!(
+                "\n  {} from {}",
+                command.path,
+                file.path.display()
+            ));
+        }
+    }
+    note
+}
+
+/// Returns the paths of every collected command, as the collection macros of tauri-helper would
+/// register them from the crate being compiled.
+///
+/// ```rust,ignore
+/// #[proc_macro]
+/// pub fn my_handler(_item: TokenStream) -> TokenStream {
+///     let paths = tauri_helper_core::macro_support::collected_command_paths();
+///     quote::quote! { tauri::generate_handler![ #(#paths),* ] }.into()
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a command file holds a path that isn't a valid Rust path.
+pub fn collected_command_paths() -> Vec<syn::Path> {
+    command_paths(&collect_commands(calling_crate()))
+}
+
+fn command_paths(commands: &BTreeSet<CommandInfo>) -> Vec<syn::Path> {
+    commands
+        .iter()
+        .map(|command| syn::parse_str(&command.path).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn attribution_note_lists_the_file_of_every_command() {
+        let files = [
+            CommandFile {
+                path: PathBuf::from("target/tauri_commands_list/app.txt"),
+                commands: vec![CommandInfo::new("app::greet"), CommandInfo::new("app::sum")],
+            },
+            CommandFile {
+                path: PathBuf::from("target/tauri_commands_list/users.txt"),
+                commands: vec![CommandInfo::new("users::load_user")],
+            },
+        ];
+
+        assert_eq!(
+            attribution_note(&files),
+            "Note: tauri-helper collected the following commands:
+  app::greet from target/tauri_commands_list/app.txt
+  app::sum from target/tauri_commands_list/app.txt
+  users::load_user from target/tauri_commands_list/users.txt"
+        );
+    }
+
+    #[test]
+    fn command_paths_are_parsed() {
+        let commands = normalize_commands(
+            "app",
+            "app",
+            vec![
+                CommandInfo::new("app::users::greet"),
+                CommandInfo::new("plugin::r#move"),
+            ],
+        );
+
+        let paths: Vec<String> = command_paths(&commands)
+            .iter()
+            .map(|path| {
+                let segments: Vec<String> = path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect();
+                segments.join("::")
+            })
+            .collect();
+        assert_eq!(paths, ["crate::users::greet", "plugin::r#move"]);
+    }
+
+    #[test]
+    fn debug_report_describes_the_discovery() {
+        let workspace = tempfile::tempdir().unwrap();
+        let commands_dir = workspace.path().join("target/tauri_commands_list");
+        fs::create_dir_all(&commands_dir).unwrap();
+        let files = [CommandFile {
+            path: commands_dir.join("app.txt"),
+            commands: vec![
+                CommandInfo::new("app::greet"),
+                CommandInfo {
+                    metadata_only: true,
+                    ..CommandInfo::new("docs::describe")
+                },
+            ],
+        }];
+
+        let report = debug_report(workspace.path(), &commands_dir, Some(&files));
+        assert_eq!(report["commands_dir"], "target/tauri_commands_list");
+        assert_eq!(report["commands_dir_found"], true);
+        assert_eq!(
+            report["files"],
+            json!([{ "path": "target/tauri_commands_list/app.txt", "commands": 2 }])
+        );
+        assert_eq!(
+            report["collected"],
+            json!([{ "path": "app::greet", "name": "greet" }])
+        );
+        assert_eq!(
+            report["dropped"],
+            json!([{ "path": "docs::describe", "reason": "metadata_only" }])
+        );
+
+        let missing = workspace.path().join("missing");
+        let report = debug_report(workspace.path(), &missing, None);
+        assert_eq!(report["commands_dir_found"], false);
+        assert_eq!(report["files"], json!([]));
+    }
+
+    #[test]
+    fn command_paths_are_sorted_whatever_the_order_of_the_files() {
+        let app = [CommandInfo::new("app::sum"), CommandInfo::new("app::greet")];
+        let users = [
+            CommandInfo::new("users::load_user"),
+            CommandInfo::new("users::delete_user"),
+        ];
+        let paths = |commands: Vec<CommandInfo>| -> Vec<String> {
+            command_paths(&normalize_commands("app", "app", commands))
+                .iter()
+                .map(|path| {
+                    let segments: Vec<String> = path
+                        .segments
+                        .iter()
+                        .map(|segment| segment.ident.to_string())
+                        .collect();
+                    segments.join("::")
+                })
+                .collect()
+        };
+
+        let sorted = paths(app.iter().chain(&users).cloned().collect());
+        assert_eq!(
+            sorted,
+            [
+                "crate::greet",
+                "crate::sum",
+                "users::delete_user",
+                "users::load_user"
+            ]
+        );
+        assert_eq!(paths(users.iter().chain(&app).cloned().collect()), sorted);
+    }
+}
@@ -1,6 +1,9 @@
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Debug, Deserialize)]
+#[serde(try_from = "RawCargoToml")]
 pub struct CargoToml {
     #[allow(dead_code)]
     pub package: Package,
@@ -22,6 +25,170 @@ pub struct Workspace {
     pub members: Vec<String>,
 }
 
+/// What to do when several commands are registered under the same name, see
+/// [`TauriHelperOptions::on_duplicate`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Fails the build, listing the duplicates.
+    Error,
+    /// Keeps every command and warns, they clash when the handler is generated.
+    #[default]
+    Warn,
+    /// Keeps the first command found and drops the others with a warning.
+    FirstWins,
+    /// Keeps the last command found and drops the others with a warning.
+    LastWins,
+}
+
+/// Convention the exposed names of the commands must follow, see
+/// [`TauriHelperOptions::name_convention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingConvention {
+    /// `load_settings`
+    SnakeCase,
+    /// `loadSettings`
+    CamelCase,
+    /// `LoadSettings`
+    PascalCase,
+}
+
+impl NamingConvention {
+    /// Returns `true` if `name` follows the convention.
+    pub fn matches(self, name: &str) -> bool {
+        let Some(first) = name.chars().next() else {
+            return false;
+        };
+        let alphanumeric = name.chars().all(|c| c.is_ascii_alphanumeric());
+        match self {
+            Self::SnakeCase => name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            Self::CamelCase => first.is_ascii_lowercase() && alphanumeric,
+            Self::PascalCase => first.is_ascii_uppercase() && alphanumeric,
+        }
+    }
+}
+
+/// Manifest of a workspace member, only its name and the parts describing its targets.
+#[derive(Debug, Default, Deserialize)]
+pub struct MemberManifest {
+    pub package: Option<MemberPackage>,
+    pub lib: Option<Target>,
+    #[serde(default)]
+    pub bin: Vec<Target>,
+}
+
+/// The `[package]` of a member.
+#[derive(Debug, Deserialize)]
+pub struct MemberPackage {
+    pub name: String,
+}
+
+/// A `[lib]` or `[[bin]]` target of a member.
+#[derive(Debug, Default, Deserialize)]
+pub struct Target {
+    pub name: Option<String>,
+    /// Path of the crate root relative to the member directory.
+    pub path: Option<String>,
+}
+
+/// `Cargo.toml` as written, before the fields inherited from `[workspace.package]` are resolved.
+#[derive(Deserialize)]
+struct RawCargoToml {
+    package: RawPackage,
+    /// Missing for single-crate apps.
+    workspace: Option<RawWorkspace>,
+}
+
+#[derive(Deserialize)]
+struct RawPackage {
+    name: String,
+    version: Option<Inheritable>,
+    edition: Option<Inheritable>,
+}
+
+#[derive(Deserialize)]
+struct RawWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    package: WorkspacePackage,
+}
+
+#[derive(Default, Deserialize)]
+struct WorkspacePackage {
+    version: Option<String>,
+    edition: Option<String>,
+}
+
+/// A package field that is either set directly or inherited with `{ workspace = true }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Inheritable {
+    Value(String),
+    Inherited { workspace: bool },
+}
+
+impl Inheritable {
+    fn resolve(
+        field: Option<Self>,
+        name: &str,
+        inherited: Option<&String>,
+        default: &str,
+    ) -> Result<String, String> {
+        match field {
+            Some(Self::Value(value)) => Ok(value),
+            Some(Self::Inherited { workspace: true }) => inherited.cloned().ok_or_else(|| {
+                format!(
+                    "`package.{}` is inherited from the workspace but `workspace.package.{}` is not set",
+                    name, name
+                )
+            }),
+            Some(Self::Inherited { workspace: false }) => {
+                Err(format!("`package.{}.workspace` can only be `true`", name))
+            }
+            // Cargo's defaults when the field is omitted
+            None => Ok(default.to_string()),
+        }
+    }
+}
+
+impl TryFrom<RawCargoToml> for CargoToml {
+    type Error = String;
+
+    fn try_from(raw: RawCargoToml) -> Result<Self, Self::Error> {
+        // a package without a workspace is its only member
+        let workspace = raw.workspace.unwrap_or_else(|| RawWorkspace {
+            members: vec![".".to_string()],
+            package: WorkspacePackage::default(),
+        });
+        let inherited = &workspace.package;
+
+        Ok(Self {
+            package: Package {
+                name: raw.package.name,
+                version: Inheritable::resolve(
+                    raw.package.version,
+                    "version",
+                    inherited.version.as_ref(),
+                    "0.0.0",
+                )?,
+                edition: Inheritable::resolve(
+                    raw.package.edition,
+                    "edition",
+                    inherited.edition.as_ref(),
+                    "2015",
+                )?,
+            },
+            workspace: Workspace {
+                members: workspace.members,
+            },
+        })
+    }
+}
+
 /// Configuration options for the `tauri_helper` crate.
 ///
 /// This struct allows you to customize the behavior of the command collection process.
@@ -45,7 +212,7 @@ pub struct TauriHelperOptions {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// #[tauri::command]
     /// #[auto_collect_command] // Explicitly opt-in to command collection
     /// fn my_command() {
@@ -57,8 +224,224 @@ pub struct TauriHelperOptions {
     /// want to automatically collect all `#[tauri::command]` functions without explicit
     /// opt-in. Use this option with caution.
     pub collect_all: bool,
+    /// Makes the build fail when a `#[tauri::command]` function lacks the marker attribute,
+    /// listing every such function with its location.
+    ///
+    /// This enforces the explicit opt-in recommended above, it has no effect with `collect_all`.
+    #[serde(default)]
+    pub require_annotation: bool,
+    /// Makes the build fail on any condition that would otherwise be reported as a build warning
+    /// (unparsable file, skipped large file, shadowed command, ...), listing all of them.
+    #[serde(default)]
+    pub deny_warnings: bool,
+    /// What to do when commands of the workspace are registered under the same name, aliases
+    /// included, e.g. two crates defining a `greet` command. Warns by default.
+    ///
+    /// Commands are ordered by crate name, then in the order they are found in the crate, which
+    /// decides the command kept by `FirstWins` and `LastWins`.
+    #[serde(default)]
+    pub on_duplicate: DuplicatePolicy,
+    /// Convention the names commands are exposed under, aliases included, must follow. Every name
+    /// that doesn't is reported as a warning, enable `deny_warnings` to fail the build instead.
+    #[serde(default)]
+    pub name_convention: Option<NamingConvention>,
     /// Members of the workspace to scan, if `None` it will just scan every member
     pub members: Option<Vec<String>>,
+    /// Packages of the workspace to scan, by the `name` of their `[package]`, resolved to the
+    /// members defining them. Unlike `members`, renaming the directory of a package doesn't
+    /// require updating it.
+    ///
+    /// When `members` is `None`, only these packages are scanned, otherwise they are scanned along
+    /// with the listed members.
+    #[serde(default)]
+    pub scan_packages: Vec<String>,
+    /// Other crate directories to scan, relative to the workspace root or absolute, e.g. a
+    /// `path = "../shared"` dependency living outside of the workspace.
+    ///
+    /// They are scanned like members, the name of the directory being used as the crate name.
+    #[serde(default)]
+    pub extra_scan_paths: Vec<PathBuf>,
+    /// Crates whose commands are scanned and written to the metadata outputs (JSON, schemas, ...)
+    /// but not registered by the collection macros, e.g. docs-only or plugin-candidate crates.
+    ///
+    /// Crates are named like their command files, after their directory.
+    #[serde(default)]
+    pub metadata_only_crates: Vec<String>,
+    /// Central rename table mapping the path of a command, as written in the command files
+    /// (e.g. `my_crate::greet`), to the name it is exposed under.
+    ///
+    /// A command that is also renamed with `#[auto_collect_command(rename = "...")]` to a
+    /// different name makes the build fail.
+    #[serde(default)]
+    pub rename_map: HashMap<String, String>,
+    /// Maps a crate, by the name of its command file, to the module its commands are re-exported
+    /// from, e.g. `plugin` to `my_app::plugin_commands` for an app doing
+    /// `pub mod plugin_commands { pub use plugin::commands::*; }`.
+    ///
+    /// The commands of the crate are then referred to by their name in that module, e.g.
+    /// `my_app::plugin_commands::greet` instead of `plugin::commands::greet`, the rename map
+    /// being looked up with the new path.
+    #[serde(default)]
+    pub reexport_paths: HashMap<String, String>,
+    /// Prefix added to the name every command is registered under, aliases included, e.g. `app_`
+    /// to register `greet` as `app_greet` and avoid clashes with the commands of plugins.
+    ///
+    /// It is applied after the renames, `#[auto_collect_command(rename = "hello")]` gives
+    /// `app_hello`. Metadata-only commands are registered elsewhere and keep their name.
+    #[serde(default)]
+    pub name_prefix: Option<String>,
+    /// Whether to collect commands that only exist in test builds.
+    ///
+    /// Functions and inline modules gated with `#[cfg(test)]`, such as the usual `mod tests`,
+    /// are skipped by default since they are never compiled into the application.
+    #[serde(default)]
+    pub include_test_commands: bool,
+    /// Whether to collect private functions.
+    ///
+    /// Only functions with a visibility (`pub`, `pub(crate)`, ...) are collected by default since
+    /// the handler can't reach private ones from another module. Enable this when the handler is
+    /// generated in the module defining the commands.
+    #[serde(default)]
+    pub include_private: bool,
+    /// Prints a summary of the scan (files scanned, commands found per crate and elapsed time)
+    /// as build warnings.
+    #[serde(default)]
+    pub verbose: bool,
+    /// Path of a C header to generate for FFI embedders, relative to the workspace root.
+    ///
+    /// The header holds a `TAURI_COMMAND_<NAME>` define per command and a `NULL`-terminated
+    /// `TAURI_COMMANDS` array listing every command name.
+    #[serde(default)]
+    pub c_header: Option<PathBuf>,
+    /// Maximum size in bytes of the source files to parse, `None` means no limit.
+    ///
+    /// Larger files, typically generated ones, are skipped with a warning instead of being
+    /// loaded in memory and parsed.
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+    /// Maximum depth of the source files to scan below the `src` directory of each member, `None`
+    /// means no limit. `src/lib.rs` is at depth 1, `src/commands/mod.rs` at depth 2.
+    ///
+    /// Deeper directories aren't walked and the commands of the files they hold, even declared
+    /// modules, aren't collected.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Finds the commands by matching the text of the source files instead of parsing them, see
+    /// the "Fast scan" section of `generate_command_file` for what it doesn't support.
+    #[serde(default)]
+    pub fast_scan: bool,
+    /// Path of a file caching the scan of every source file, relative to the workspace root, e.g.
+    /// `target/tauri-helper-cache.json`.
+    ///
+    /// Files are only parsed again when their content changes, modification times are ignored so
+    /// that the cache keeps working on fresh checkouts, e.g. when restored in CI.
+    #[serde(default)]
+    pub scan_cache: Option<PathBuf>,
+    /// Maximum number of threads scanning the workspace, `None` for one per CPU.
+    ///
+    /// The members and their files share the same threads, bounding the number of files open at
+    /// once in very large workspaces.
+    #[serde(default)]
+    pub scan_threads: Option<usize>,
+    /// Starts every command file with a `# generated by tauri-helper for crate <name>` comment,
+    /// which is skipped when the files are read.
+    #[serde(default)]
+    pub command_file_header: bool,
+    /// Keeps the order of the commands already in the command files, appending new ones instead
+    /// of writing every command in the order it was found.
+    #[serde(default)]
+    pub stable_order: bool,
+    /// Tags the commands taking no arguments from the frontend, only ones injected by Tauri such
+    /// as `State` or `AppHandle`, with `no_args` in the command files and the JSON outputs, so
+    /// that frontend tooling can generate parameterless bindings for them.
+    #[serde(default)]
+    pub tag_no_args: bool,
+    /// Path of a Graphviz `.dot` file to generate, relative to the workspace root, showing which
+    /// crate defines which command and the groups of the commands.
+    #[serde(default)]
+    pub dot_graph: Option<PathBuf>,
+    /// Path of a JSON file describing every command keyed by its exposed name, relative to the
+    /// workspace root.
+    #[serde(default)]
+    pub keyed_json: Option<PathBuf>,
+    /// Path of a JSON index of the commands grouped by the module defining them to generate,
+    /// relative to the workspace root.
+    #[serde(default)]
+    pub module_index: Option<PathBuf>,
+    /// Path of a JSON file holding a JSON Schema of the value each command resolves with, keyed by
+    /// its exposed name, relative to the workspace root.
+    #[serde(default)]
+    pub return_schemas: Option<PathBuf>,
+    /// Path of a human-friendly list of the commands to generate, relative to the workspace root.
+    ///
+    /// Unlike the command files, the list is sorted and meant to be committed and reviewed.
+    #[serde(default)]
+    pub review_list: Option<PathBuf>,
+    /// Path of a SQLite database of the commands to generate, relative to the workspace root.
+    /// Requires the `sqlite` feature.
+    #[serde(default)]
+    pub sqlite_db: Option<PathBuf>,
+    /// Path of a precompiled `commands.json`, such as a copy of the `_all.json` manifest of an
+    /// earlier build, whose commands are used instead of scanning the workspace, relative to the workspace root.
+    ///
+    /// With `check_precompiled`, the workspace is scanned too and the build fails if the commands
+    /// found differ from the precompiled ones.
+    #[serde(default)]
+    pub precompiled_commands: Option<PathBuf>,
+    /// Scans the workspace even if `precompiled_commands` is set, failing the build if the
+    /// precompiled commands don't match the sources.
+    #[serde(default)]
+    pub check_precompiled: bool,
+    /// Path of a TypeScript module exporting a typed `invoke` wrapper for every command to
+    /// generate, e.g. `../src/commands.ts`, relative to the workspace root.
+    #[serde(default)]
+    pub ts_bindings: Option<PathBuf>,
+    /// Path of a TypeScript module exporting the names of the commands to generate, typically in
+    /// the sources of the frontend, e.g. `../src/generated/commands.ts`, relative to the workspace
+    /// root.
+    #[serde(default)]
+    pub frontend_commands: Option<PathBuf>,
+    /// Path of a JSON reference of the permission identifiers Tauri generates for each command to
+    /// generate, e.g. `permissions.json`, relative to the workspace root.
+    #[serde(default)]
+    pub permissions_reference: Option<PathBuf>,
+    /// Path of a signature lock to generate, e.g. `tauri-commands.lock`, relative to the
+    /// workspace root.
+    ///
+    /// The lock pins a hash of the signature of each command, meant to be committed so that
+    /// signature changes show up in code review and are reported by `validate_command_files_with`.
+    #[serde(default)]
+    pub signature_lock: Option<PathBuf>,
+    /// Writes a `tauri_builder.rs` file into `OUT_DIR` defining a `tauri_builder()` function that
+    /// returns a `tauri::Builder` with every collected command registered, to be `include!`d by
+    /// the application.
+    #[serde(default)]
+    pub builder_snippet: bool,
+    /// Writes a `tauri_command_stubs.rs` file into `OUT_DIR` defining a test that refers to every
+    /// collected command, to be `include!`d in a `#[cfg(test)]` module.
+    #[serde(default)]
+    pub test_stub: bool,
+    /// Path of a file listing the commands that must never be registered, relative to the
+    /// workspace root.
+    ///
+    /// The file holds one command name per line, blank lines and lines starting with `#` are
+    /// ignored. The build fails if a scanned command is registered under a listed name, its
+    /// aliases included, or if its full path is listed.
+    #[serde(default)]
+    pub denylist_file: Option<PathBuf>,
+    /// Name of the attribute marking the functions to collect, `auto_collect_command` by default.
+    ///
+    /// Its arguments (`platform`, `group`, `rename`, ...) are read by the scanner.
+    #[serde(default = "default_marker_attribute")]
+    pub marker_attribute: String,
+    /// Other attributes that also mark functions to collect, e.g. a legacy marker during a
+    /// migration. Their arguments are ignored.
+    #[serde(default)]
+    pub marker_aliases: Vec<String>,
+}
+
+fn default_marker_attribute() -> String {
+    "auto_collect_command".to_string()
 }
 
 #[allow(clippy::derivable_impls)]
@@ -73,7 +456,46 @@ impl Default for TauriHelperOptions {
     fn default() -> Self {
         Self {
             collect_all: false,
+            require_annotation: false,
+            deny_warnings: false,
+            on_duplicate: DuplicatePolicy::Warn,
+            name_convention: None,
             members: None,
+            scan_packages: Vec::new(),
+            extra_scan_paths: Vec::new(),
+            metadata_only_crates: Vec::new(),
+            rename_map: HashMap::new(),
+            reexport_paths: HashMap::new(),
+            name_prefix: None,
+            include_test_commands: false,
+            include_private: false,
+            verbose: false,
+            c_header: None,
+            max_file_size: None,
+            max_depth: None,
+            fast_scan: false,
+            scan_cache: None,
+            scan_threads: None,
+            command_file_header: false,
+            stable_order: false,
+            tag_no_args: false,
+            dot_graph: None,
+            keyed_json: None,
+            module_index: None,
+            return_schemas: None,
+            review_list: None,
+            signature_lock: None,
+            sqlite_db: None,
+            precompiled_commands: None,
+            check_precompiled: false,
+            ts_bindings: None,
+            frontend_commands: None,
+            permissions_reference: None,
+            builder_snippet: false,
+            test_stub: false,
+            denylist_file: None,
+            marker_attribute: default_marker_attribute(),
+            marker_aliases: Vec::new(),
         }
     }
 }
@@ -83,6 +505,361 @@ impl TauriHelperOptions {
         Self {
             collect_all,
             members,
+            ..Default::default()
         }
     }
+
+    /// Returns a builder starting from the default options.
+    ///
+    /// ```rust,ignore
+    /// let options = TauriHelperOptions::builder()
+    ///     .members(["src-tauri", "local-crates/commands"])
+    ///     .rename("my_app::greet", "hello")
+    ///     .verbose(true)
+    ///     .build();
+    /// ```
+    pub fn builder() -> TauriHelperOptionsBuilder {
+        TauriHelperOptionsBuilder::default()
+    }
+}
+
+/// Builder for [`TauriHelperOptions`], see the fields of the options for what each setter does.
+#[derive(Default)]
+pub struct TauriHelperOptionsBuilder {
+    options: TauriHelperOptions,
+}
+
+impl TauriHelperOptionsBuilder {
+    pub fn collect_all(mut self, collect_all: bool) -> Self {
+        self.options.collect_all = collect_all;
+        self
+    }
+
+    pub fn require_annotation(mut self, require_annotation: bool) -> Self {
+        self.options.require_annotation = require_annotation;
+        self
+    }
+
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.options.deny_warnings = deny_warnings;
+        self
+    }
+
+    pub fn on_duplicate(mut self, on_duplicate: DuplicatePolicy) -> Self {
+        self.options.on_duplicate = on_duplicate;
+        self
+    }
+
+    pub fn name_convention(mut self, name_convention: NamingConvention) -> Self {
+        self.options.name_convention = Some(name_convention);
+        self
+    }
+
+    pub fn members<I, S>(mut self, members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.members = Some(members.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds a package to scan, by the name of its `[package]`.
+    pub fn scan_package(mut self, package: impl Into<String>) -> Self {
+        self.options.scan_packages.push(package.into());
+        self
+    }
+
+    /// Adds a directory to scan in addition to the members.
+    pub fn extra_scan_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.extra_scan_paths.push(path.into());
+        self
+    }
+
+    /// Adds a crate whose commands are only recorded in the metadata outputs.
+    pub fn metadata_only_crate(mut self, crate_name: impl Into<String>) -> Self {
+        self.options.metadata_only_crates.push(crate_name.into());
+        self
+    }
+
+    /// Adds an entry to the rename map.
+    pub fn rename(mut self, path: impl Into<String>, name: impl Into<String>) -> Self {
+        self.options.rename_map.insert(path.into(), name.into());
+        self
+    }
+
+    /// Adds an entry to the re-export paths.
+    pub fn reexport_path(mut self, crate_name: impl Into<String>, path: impl Into<String>) -> Self {
+        self.options
+            .reexport_paths
+            .insert(crate_name.into(), path.into());
+        self
+    }
+
+    pub fn name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.options.name_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn include_test_commands(mut self, include_test_commands: bool) -> Self {
+        self.options.include_test_commands = include_test_commands;
+        self
+    }
+
+    pub fn include_private(mut self, include_private: bool) -> Self {
+        self.options.include_private = include_private;
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.options.verbose = verbose;
+        self
+    }
+
+    pub fn c_header(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.c_header = Some(path.into());
+        self
+    }
+
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.options.max_file_size = Some(max_file_size);
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn fast_scan(mut self, fast_scan: bool) -> Self {
+        self.options.fast_scan = fast_scan;
+        self
+    }
+
+    pub fn scan_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.scan_cache = Some(path.into());
+        self
+    }
+
+    pub fn scan_threads(mut self, scan_threads: usize) -> Self {
+        self.options.scan_threads = Some(scan_threads);
+        self
+    }
+
+    pub fn command_file_header(mut self, command_file_header: bool) -> Self {
+        self.options.command_file_header = command_file_header;
+        self
+    }
+
+    pub fn stable_order(mut self, stable_order: bool) -> Self {
+        self.options.stable_order = stable_order;
+        self
+    }
+
+    pub fn tag_no_args(mut self, tag_no_args: bool) -> Self {
+        self.options.tag_no_args = tag_no_args;
+        self
+    }
+
+    pub fn dot_graph(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.dot_graph = Some(path.into());
+        self
+    }
+
+    pub fn keyed_json(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.keyed_json = Some(path.into());
+        self
+    }
+
+    pub fn module_index(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.module_index = Some(path.into());
+        self
+    }
+
+    pub fn return_schemas(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.return_schemas = Some(path.into());
+        self
+    }
+
+    pub fn review_list(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.review_list = Some(path.into());
+        self
+    }
+
+    pub fn sqlite_db(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.sqlite_db = Some(path.into());
+        self
+    }
+
+    pub fn precompiled_commands(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.precompiled_commands = Some(path.into());
+        self
+    }
+
+    pub fn check_precompiled(mut self, check_precompiled: bool) -> Self {
+        self.options.check_precompiled = check_precompiled;
+        self
+    }
+
+    pub fn ts_bindings(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.ts_bindings = Some(path.into());
+        self
+    }
+
+    pub fn frontend_commands(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.frontend_commands = Some(path.into());
+        self
+    }
+
+    pub fn permissions_reference(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.permissions_reference = Some(path.into());
+        self
+    }
+
+    pub fn signature_lock(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.signature_lock = Some(path.into());
+        self
+    }
+
+    pub fn builder_snippet(mut self, builder_snippet: bool) -> Self {
+        self.options.builder_snippet = builder_snippet;
+        self
+    }
+
+    pub fn test_stub(mut self, test_stub: bool) -> Self {
+        self.options.test_stub = test_stub;
+        self
+    }
+
+    pub fn denylist_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.denylist_file = Some(path.into());
+        self
+    }
+
+    pub fn marker_attribute(mut self, marker_attribute: impl Into<String>) -> Self {
+        self.options.marker_attribute = marker_attribute.into();
+        self
+    }
+
+    /// Adds an attribute to the marker aliases.
+    pub fn marker_alias(mut self, marker_alias: impl Into<String>) -> Self {
+        self.options.marker_aliases.push(marker_alias.into());
+        self
+    }
+
+    pub fn build(self) -> TauriHelperOptions {
+        self.options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(manifest: &str) -> Result<CargoToml, toml::de::Error> {
+        toml::from_str(manifest)
+    }
+
+    #[test]
+    fn literal_package_fields() {
+        let manifest = parse(
+            r#"
+            [package]
+            name = "my-app"
+            version = "1.2.3"
+            edition = "2021"
+
+            [workspace]
+            members = ["."]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.package.name, "my-app");
+        assert_eq!(manifest.package.version, "1.2.3");
+        assert_eq!(manifest.package.edition, "2021");
+    }
+
+    #[test]
+    fn inherited_package_fields() {
+        let manifest = parse(
+            r#"
+            [package]
+            name = "my-app"
+            version.workspace = true
+            edition = { workspace = true }
+
+            [workspace]
+            members = ["."]
+
+            [workspace.package]
+            version = "0.3.0"
+            edition = "2024"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.package.version, "0.3.0");
+        assert_eq!(manifest.package.edition, "2024");
+    }
+
+    #[test]
+    fn missing_package_fields_get_cargo_defaults() {
+        let manifest = parse("[package]\nname = \"my-app\"\n").unwrap();
+        assert_eq!(manifest.package.version, "0.0.0");
+        assert_eq!(manifest.package.edition, "2015");
+    }
+
+    #[test]
+    fn inherited_field_missing_from_the_workspace() {
+        let error = parse(
+            r#"
+            [package]
+            name = "my-app"
+            version.workspace = true
+
+            [workspace]
+            members = ["."]
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("`package.version` is inherited from the workspace but `workspace.package.version` is not set"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn builder_sets_the_options() {
+        let options = TauriHelperOptions::builder()
+            .collect_all(true)
+            .members(["src-tauri", "local-crates/commands"])
+            .rename("app::greet", "hello")
+            .on_duplicate(DuplicatePolicy::FirstWins)
+            .max_depth(3)
+            .build();
+
+        assert!(options.collect_all);
+        assert_eq!(
+            options.members.as_deref(),
+            Some(&["src-tauri".to_string(), "local-crates/commands".to_string()][..])
+        );
+        assert_eq!(
+            options.rename_map.get("app::greet").map(String::as_str),
+            Some("hello")
+        );
+        assert_eq!(options.on_duplicate, DuplicatePolicy::FirstWins);
+        assert_eq!(options.max_depth, Some(3));
+        // the other options keep their default
+        assert_eq!(
+            options.marker_attribute,
+            TauriHelperOptions::default().marker_attribute
+        );
+        assert!(!options.verbose);
+
+        let constructed = TauriHelperOptions::new(true, Some(vec!["app".to_string()]));
+        assert!(constructed.collect_all);
+        assert_eq!(constructed.members, Some(vec!["app".to_string()]));
+    }
 }
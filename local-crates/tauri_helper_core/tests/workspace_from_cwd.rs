@@ -0,0 +1,36 @@
+//! Finds the workspace from the current directory, which is changed for the whole process, so
+//! this is the only test of its binary.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use tauri_helper_core::{get_workspace, get_workspace_from_cwd};
+
+#[test]
+fn workspace_is_found_from_the_current_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"xtask\"]\n",
+    )
+    .unwrap();
+    let xtask = dir.path().join("xtask");
+    fs::create_dir_all(&xtask).unwrap();
+    fs::write(
+        xtask.join("Cargo.toml"),
+        "[package]\nname = \"xtask\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    // tests are run from their crate, the manifest dir is that of `tauri_helper_core`
+    env::set_current_dir(&xtask).unwrap();
+    assert_eq!(
+        env::var_os("CARGO_MANIFEST_DIR").as_deref(),
+        Some(Path::new(env!("CARGO_MANIFEST_DIR")).as_os_str())
+    );
+
+    let workspace = get_workspace_from_cwd();
+    assert_eq!(workspace.package.name, "app");
+    assert_eq!(workspace.workspace.members, ["xtask"]);
+    assert_eq!(get_workspace().package.name, "tauri-helper");
+}
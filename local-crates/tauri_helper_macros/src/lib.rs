@@ -1,23 +1,28 @@
 use proc_macro::TokenStream;
+#[cfg(feature = "tracing")]
+use proc_macro_error::abort;
 use proc_macro_error::proc_macro_error;
-use quote::quote;
-use std::path::Path;
-use std::{
-    collections::HashSet,
-    env,
-    fs::{self},
-};
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use std::collections::{BTreeSet, HashMap};
 #[cfg(feature = "tracing")]
 use syn::{Data, DeriveInput, Fields};
-use syn::{ItemFn, LitBool, parse_macro_input};
-use tauri_helper_core::{find_workspace_dir, get_workspace_pkg_name};
+use syn::{
+    ItemFn, ItemMod, LitBool, LitStr, Token, ext::IdentExt, parse_macro_input,
+    punctuated::Punctuated,
+};
+use tauri_helper_core::args::CommandArgs;
+use tauri_helper_core::command::CommandInfo;
+use tauri_helper_core::macro_support::{
+    calling_crate, collect_commands, collect_commands_with_full_paths,
+};
 
 #[cfg(feature = "tracing")]
 fn is_string_type(ty: &syn::Type) -> bool {
-    if let syn::Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "String";
-        }
+    if let syn::Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return segment.ident == "String";
     }
     false
 }
@@ -30,7 +35,7 @@ fn is_string_type(ty: &syn::Type) -> bool {
 /// # Example
 ///
 /// ```rust
-/// use tracing::error;
+/// use tauri_helper_macros::WithLogging;
 ///
 /// #[derive(WithLogging)]
 /// enum Error {
@@ -43,18 +48,128 @@ fn is_string_type(ty: &syn::Type) -> bool {
 ///     StructError { code: i32, message: String },
 /// }
 /// ```
+///
+/// Generic enums are supported, the generics and `where` clause of the enum are repeated on each
+/// impl along with the bounds the conversion needs (`Into`, `Display` or `Debug` on the types involved):
+///
+/// ```rust
+/// use tauri_helper_macros::WithLogging;
+///
+/// #[derive(WithLogging)]
+/// enum Error<T>
+/// where
+///     T: std::fmt::Debug,
+/// {
+///     #[logging_from(String)]
+///     Message(String),
+///
+///     Context(T, String),
+/// }
+/// ```
+///
+/// With the `backtrace` feature enabled, `#[logging(backtrace)]` captures a
+/// `std::backtrace::Backtrace` in every conversion and records it as the `backtrace` field of the
+/// event, which shows where the error was created. Capturing is costly, keep it to debugging.
+/// Without the feature the option is ignored:
+///
+/// ```rust
+/// use tauri_helper_macros::WithLogging;
+///
+/// #[derive(WithLogging)]
+/// #[logging(backtrace)]
+/// enum Error {
+///     #[logging_from(String)]
+///     StringError(String),
+/// }
+/// ```
+///
+/// The value of a single-field variant is logged with `Display`. `#[logging(debug)]` on the variant,
+/// or on the enum for every variant, logs it with `Debug` instead, for types that don't implement
+/// `Display`:
+///
+/// ```rust
+/// use tauri_helper_macros::WithLogging;
+///
+/// #[derive(Debug)]
+/// struct Code(u16);
+///
+/// #[derive(WithLogging)]
+/// enum Error {
+///     #[logging_from(Code)]
+///     #[logging(debug)]
+///     CodeError(Code),
+/// }
+/// ```
+///
+/// `#[non_exhaustive]` enums are supported as well: the generated impls only construct variants and
+/// never match on the enum, so adding a variant never breaks them.
+///
+/// Unit variants get no impl, `#[logging_from]` on one is an error since it has no field to hold
+/// the converted value.
+///
+/// Explicit discriminants are supported and left untouched, they have no effect on the generated impls:
+///
+/// ```rust
+/// use tauri_helper_macros::WithLogging;
+///
+/// #[derive(WithLogging)]
+/// #[repr(u8)]
+/// enum Error {
+///     Unknown = 1,
+///
+///     #[logging_from(String)]
+///     StringError(String) = 2,
+/// }
+/// ```
 #[cfg(feature = "tracing")]
 #[proc_macro_error]
-#[proc_macro_derive(WithLogging, attributes(logging_from, no_from_string))]
+#[proc_macro_derive(WithLogging, attributes(logging, logging_from, no_from_string))]
 pub fn derive_with_logging(input: TokenStream) -> TokenStream {
-    use proc_macro_error::proc_macro_error;
-
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
     let mut from_impls = vec![];
 
+    let mut backtrace = false;
+    let mut debug = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("logging")) {
+        if let Err(e) = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("backtrace") {
+                backtrace = true;
+                Ok(())
+            } else if meta.path.is_ident("debug") {
+                debug = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `logging` option, expected `backtrace` or `debug`"))
+            }
+        }) {
+            return e.to_compile_error().into();
+        }
+    }
+    // without the `backtrace` feature the option is accepted and ignored
+    backtrace &= cfg!(feature = "backtrace");
+
+    // The backtrace is captured in the conversion and recorded as a field of the event
+    let (capture, backtrace_field) = if backtrace {
+        (
+            quote! { let backtrace = ::std::backtrace::Backtrace::force_capture(); },
+            quote! { backtrace = %backtrace, },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // The where clause of the enum, extended with the bounds the conversion needs
+    let where_clause = |bounds: Vec<syn::WherePredicate>| {
+        let mut generics = input.generics.clone();
+        generics.make_where_clause().predicates.extend(bounds);
+        generics.where_clause
+    };
+
     if let Data::Enum(ref data_enum) = input.data {
         for variant in &data_enum.variants {
+            // Only the fields matter here, an explicit discriminant (`Variant = 5`) is kept as is on the enum.
             let variant_name = &variant.ident;
 
             match &variant.fields {
@@ -68,22 +183,60 @@ pub fn derive_with_logging(input: TokenStream) -> TokenStream {
                         let convert_type = attr.parse_args::<syn::Type>().unwrap();
                         let field_type = &fields.unnamed.first().unwrap().ty;
 
-                        let conversion = if is_string_type(field_type) {
-                            quote! { value.to_string() }
+                        let mut debug = debug;
+                        for attr in variant
+                            .attrs
+                            .iter()
+                            .filter(|a| a.path().is_ident("logging"))
+                        {
+                            if let Err(e) = attr.parse_nested_meta(|meta| {
+                                if meta.path.is_ident("debug") {
+                                    debug = true;
+                                    Ok(())
+                                } else {
+                                    Err(meta.error(
+                                        "unknown `logging` option on a variant, expected `debug`",
+                                    ))
+                                }
+                            }) {
+                                return e.to_compile_error().into();
+                            }
+                        }
+                        // Whether the field implements `Display` can't be known here, the user opts into `Debug`
+                        let (format, format_bound) = if debug {
+                            ("Error occurred: {} - {:?}", quote! { ::std::fmt::Debug })
+                        } else {
+                            ("Error occurred: {} - {}", quote! { ::std::fmt::Display })
+                        };
+
+                        let (conversion, bound) = if is_string_type(field_type) {
+                            (
+                                quote! { value.to_string() },
+                                syn::parse_quote! { #convert_type: ::std::string::ToString },
+                            )
                         } else {
-                            quote! { value.into() }
+                            (
+                                quote! { value.into() },
+                                syn::parse_quote! { #convert_type: ::std::convert::Into<#field_type> },
+                            )
                         };
+                        let where_clause = where_clause(vec![
+                            bound,
+                            syn::parse_quote! { #field_type: #format_bound },
+                        ]);
 
                         let from_impl = quote! {
-                            impl From<#convert_type> for #name {
+                            impl #impl_generics From<#convert_type> for #name #ty_generics #where_clause {
                                 fn from(value: #convert_type) -> Self {
                                     let converted_value: #field_type = #conversion;
+                                    #capture
                                     tracing::error!(
-                                        "Error occurred: {} - {}",
+                                        #backtrace_field
+                                        #format,
                                         stringify!(#variant_name),
                                         converted_value
                                     );
-                                    #name::#variant_name(converted_value)
+                                    Self::#variant_name(converted_value)
                                 }
                             }
                         };
@@ -99,13 +252,21 @@ pub fn derive_with_logging(input: TokenStream) -> TokenStream {
                         })
                         .collect();
 
+                    let where_clause = where_clause(
+                        field_types
+                            .iter()
+                            .map(|ty| syn::parse_quote! { #ty: ::std::fmt::Debug })
+                            .collect(),
+                    );
+
                     let from_impl = quote! {
-                        impl From<(#(#field_types),*)> for #name {
+                        impl #impl_generics From<(#(#field_types),*)> for #name #ty_generics #where_clause {
                             fn from(value: (#(#field_types),*)) -> Self {
                                 let (#(#field_names),*) = value;
-                                let err_str = format!("{:?}", (#(#field_names),*));
-                                tracing::error!("Error occurred: {} - {}", stringify!(#variant_name), err_str);
-                                #name::#variant_name(#(#field_names),*)
+                                let err_str = format!("{:?}", (#(&#field_names),*));
+                                #capture
+                                tracing::error!(#backtrace_field "Error occurred: {} - {}", stringify!(#variant_name), err_str);
+                                Self::#variant_name(#(#field_names),*)
                             }
                         }
                     };
@@ -117,28 +278,53 @@ pub fn derive_with_logging(input: TokenStream) -> TokenStream {
                     let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
                     let field_types: Vec<_> = fields.named.iter().map(|f| &f.ty).collect();
 
+                    let where_clause = where_clause(
+                        field_types
+                            .iter()
+                            .map(|ty| syn::parse_quote! { #ty: ::std::fmt::Debug })
+                            .collect(),
+                    );
+
                     let from_impl = quote! {
-                        impl From<(#(#field_types),*)> for #name {
+                        impl #impl_generics From<(#(#field_types),*)> for #name #ty_generics #where_clause {
                             fn from(value: (#(#field_types),*)) -> Self {
                                 let (#(#field_names),*) = value;
                                 let err_str = format!("{:?}", (#(&#field_names),*));
+                                #capture
                                 tracing::error!(
+                                    #backtrace_field
                                     "Error occurred: {} - {}",
                                     stringify!(#variant_name),
                                     err_str
                                 );
-                                #name::#variant_name { #(#field_names),* }
+                                Self::#variant_name { #(#field_names),* }
                             }
                         }
                     };
                     from_impls.push(from_impl);
                 }
 
-                _ => {}
+                // Unit variants hold no value to convert into
+                Fields::Unit => {
+                    if let Some(attr) = variant
+                        .attrs
+                        .iter()
+                        .find(|a| a.path().is_ident("logging_from"))
+                    {
+                        abort!(
+                            attr,
+                            "`#[logging_from]` can't be used on the unit variant `{}`, it has no field to hold the converted value",
+                            variant_name;
+                            help = "add a field to the variant, e.g. `{}(String)`", variant_name
+                        );
+                    }
+                }
             }
         }
     }
 
+    // Only `From` impls are generated. Any `match` over the enum added here must end with a
+    // `_ =>` arm, `#[non_exhaustive]` enums can gain variants.
     let expanded = quote! {
         #(#from_impls)*
     };
@@ -147,10 +333,27 @@ pub fn derive_with_logging(input: TokenStream) -> TokenStream {
 }
 
 /// Marks a Tauri command and registers it for automatic collection
+///
+/// The command can be restricted to a platform with `#[auto_collect_command(platform = "mobile")]`,
+/// see `tauri_collect_commands_for_platform!`, and put in a group with `#[auto_collect_command(group = "settings")]`.
+///
+/// `#[auto_collect_command(timeout_ms = 5000)]` records a default timeout for frontends in the
+/// generated metadata, it isn't enforced by the backend.
+///
+/// It can be written before or after `#[tauri::command]`, the function is returned untouched and
+/// the scanner finds both attributes wherever they are.
+#[proc_macro_error]
 #[proc_macro_attribute]
-pub fn auto_collect_command(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn auto_collect_command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = CommandArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse_meta(meta));
+    parse_macro_input!(attr with parser);
+
     let input = parse_macro_input!(item as ItemFn);
-    let fn_name = input.sig.ident.to_string();
+    if let Err(e) = args.check_function(&input.sig.ident) {
+        return e.to_compile_error().into();
+    }
+    let fn_name = input.sig.ident.unraw().to_string();
 
     if !fn_name
         .chars()
@@ -163,59 +366,41 @@ pub fn auto_collect_command(_attr: TokenStream, item: TokenStream) -> TokenStrea
     quote! { #input }.into()
 }
 
-/// Collects all Tauri commands from the workspace's command files
-fn collect_commands(calling_crate: String) -> HashSet<String> {
-    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
-    let workspace_root = find_workspace_dir(Path::new(&manifest_dir));
-    let commands_dir = workspace_root.join("target").join("tauri_commands_list");
-
-    let mut commands = HashSet::new();
-
-    if let Ok(entries) = fs::read_dir(&commands_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("txt") {
-                let crate_name = get_workspace_pkg_name();
-
-                if let Ok(content) = fs::read_to_string(&path) {
-                    for line in content.lines() {
-                        let mut fn_name = line.trim().to_string();
-
-                        // Strip prefix ONLY if it's the calling crate
-                        if crate_name.replace("-", "_") == calling_crate.replace("-", "_")
-                            && let Some(stripped) =
-                                fn_name.strip_prefix(&format!("{}::", crate_name.replace("-", "_")))
-                        {
-                            fn_name = stripped.to_string();
-                        }
-
-                        if fn_name
-                            .chars()
-                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
-                        {
-                            commands.insert(fn_name);
-                        } else {
-                            panic!("Invalid function name `{}` in command file", fn_name);
-                        }
-                    }
-                }
-            }
-        }
-    } else {
-        eprintln!(
-            "Warning: No commands directory found at {}",
-            commands_dir.display()
-        );
-    }
+/// Registers the listed functions of an inline module for automatic collection, as an alternative
+/// to annotating each of them with `#[auto_collect_command]`.
+///
+/// ```rust,ignore
+/// #[auto_collect_commands(greet, calculate_sum)]
+/// pub mod commands {
+///     #[tauri::command]
+///     pub fn greet(name: String) -> String {
+///         format!("Hello, {}!", name)
+///     }
+///
+///     #[tauri::command]
+///     pub fn calculate_sum(a: i32, b: i32) -> i32 {
+///         a + b
+///     }
+/// }
+/// ```
+///
+/// Only the functions defined directly in the module can be listed, a listed name the module
+/// doesn't define is reported as a build warning.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn auto_collect_commands(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let parser = Punctuated::<syn::Ident, Token![,]>::parse_terminated;
+    parse_macro_input!(attr with parser);
+    let input = parse_macro_input!(item as ItemMod);
 
-    commands
+    // Returns the original module
+    quote! { #input }.into()
 }
 
 /// Generates the Specta collect_commands![] macro invocation with a list of all collected commands.
 #[proc_macro]
 pub fn specta_collect_commands(_item: TokenStream) -> TokenStream {
-    let calling_crate = get_workspace_pkg_name();
-    let commands = collect_commands(calling_crate);
+    let commands = collect_commands(calling_crate());
 
     if commands.is_empty() {
         eprintln!(
@@ -224,8 +409,8 @@ pub fn specta_collect_commands(_item: TokenStream) -> TokenStream {
         return quote! { tauri_specta::collect_commands![] }.into();
     }
 
-    let collected = commands.iter().map(|fn_name| {
-        let path = syn::parse_str::<syn::Path>(fn_name).unwrap();
+    let collected = commands.iter().map(|command| {
+        let path = syn::parse_str::<syn::Path>(&command.path).unwrap();
         quote!(#path)
     });
 
@@ -235,40 +420,389 @@ pub fn specta_collect_commands(_item: TokenStream) -> TokenStream {
     .into()
 }
 
-/// Generates the Tauri generate_handler![] macro invocation with a list of all collected commands.
-#[proc_macro]
-pub fn tauri_collect_commands(_item: TokenStream) -> TokenStream {
-    let calling_crate = get_workspace_pkg_name();
-    let commands = collect_commands(calling_crate);
+/// Re-exports a command under another name and returns the path to register.
+///
+/// Tauri registers a command under the last segment of its path and expects the wrapper macro
+/// `__cmd__<name>` next to it, so renamed and aliased commands are re-exported under their new name.
+fn reexport(
+    path: &syn::Path,
+    command: &CommandInfo,
+    name: &str,
+    cfg: &[proc_macro2::TokenStream],
+    aliases: &mut Vec<proc_macro2::TokenStream>,
+) -> syn::Path {
+    // unlike `Ident::new`, parsing accepts raw identifiers
+    let fn_ident: syn::Ident = syn::parse_str(command.fn_name()).unwrap();
+    let exposed = syn::Ident::new(name, Span::call_site());
+    let wrapper = format_ident!("__cmd__{}", fn_ident);
+    let exposed_wrapper = format_ident!("__cmd__{}", exposed);
+
+    let mut module = path.clone();
+    module.segments.pop();
+    module.segments.pop_punct();
+    if module.segments.is_empty() {
+        // paths without a module are referred to from where the macro is invoked
+        module = syn::parse_quote!(super);
+    }
 
-    if commands.is_empty() {
+    aliases.push(quote! {
+        #(#cfg)*
+        use #module::{#fn_ident as #exposed, #wrapper as #exposed_wrapper};
+    });
+    syn::Path::from(exposed)
+}
+
+/// Returns the `#[cfg(...)]` attributes guarding a command, so that the handler only registers it
+/// when it exists.
+///
+/// The predicates are evaluated in the crate invoking the macro, features of another crate must be
+/// forwarded by features of the same name.
+fn cfg_attributes(command: &CommandInfo) -> Vec<proc_macro2::TokenStream> {
+    command
+        .cfg
+        .iter()
+        .map(|cfg| {
+            let predicate: syn::Meta = syn::parse_str(cfg)
+                .unwrap_or_else(|e| panic!("Invalid cfg `{}` of `{}`: {}", cfg, command.path, e));
+            quote! { #[cfg(#predicate)] }
+        })
+        .collect()
+}
+
+/// Builds the `tauri::generate_handler![]` invocation for the given commands.
+fn tauri_handler<'a>(commands: impl Iterator<Item = &'a CommandInfo>, ping: bool) -> TokenStream {
+    let Some(module) = handler_module(commands, ping) else {
+        return quote! { tauri::generate_handler![] }.into();
+    };
+
+    // call the function of the hidden module. This keeps the expansion small at the call site because RA seems to panic when this happens.
+    let expanded = quote! {
+        {
+            #module
+
+            __tauri_helper_generated::__tauri_collected_handler()
+        }
+    };
+
+    expanded.into()
+}
+
+/// Builds the hidden `__tauri_helper_generated` module holding a function that returns the
+/// handler, `None` if there are no commands.
+///
+/// With `ping`, the module also defines the `__tauri_helper_ping` command and registers it.
+fn handler_module<'a>(
+    commands: impl Iterator<Item = &'a CommandInfo>,
+    ping: bool,
+) -> Option<proc_macro2::TokenStream> {
+    let mut aliases = Vec::new();
+    let mut collected_paths = Vec::new();
+
+    for command in commands {
+        let path = syn::parse_str::<syn::Path>(&command.path).unwrap();
+        let cfg = cfg_attributes(command);
+
+        let registered = if command.name.is_none() {
+            path.clone()
+        } else {
+            reexport(&path, command, command.exposed_name(), &cfg, &mut aliases)
+        };
+        collected_paths.push(quote! { #(#cfg)* #registered });
+
+        for alias in &command.aliases {
+            let registered = reexport(&path, command, alias, &cfg, &mut aliases);
+            collected_paths.push(quote! { #(#cfg)* #registered });
+        }
+    }
+
+    if collected_paths.is_empty() {
         eprintln!(
             "Warning: No commands were collected. Ensure functions are annotated with `#[auto_collect_command]`."
         );
-        return quote! { tauri::generate_handler![] }.into();
+        if !ping {
+            return None;
+        }
     }
 
-    let collected_paths = commands
-        .iter()
-        .map(|fn_name| syn::parse_str::<syn::Path>(fn_name).unwrap())
-        .collect::<Vec<_>>();
+    let ping_command = if ping {
+        collected_paths.push(quote! { __tauri_helper_ping });
+        let version = env!("CARGO_PKG_VERSION");
+        Some(quote! {
+            /// Answers with the version of the tauri-helper macros, to check the IPC wiring.
+            #[tauri::command]
+            pub fn __tauri_helper_ping() -> String {
+                #version.to_string()
+            }
+        })
+    } else {
+        None
+    };
 
-    // generate a hidden module with a function that returns the handler,then call the function. This keeps the expansion small at the call site because RA seems to panic when this happens.
-    let expanded = quote! {
+    Some(quote! {
         // hidden module to reduce type-complexity visible at call-site
         #[doc(hidden)]
         pub mod __tauri_helper_generated {
+            #(#[allow(unused_imports)] #aliases)*
+
+            #ping_command
+
             // avoid name collisions and loud lints
             #[allow(non_snake_case, dead_code, unused_imports)]
             pub fn __tauri_collected_handler() -> tauri::InvokeHandler {
                 tauri::generate_handler![ #(#collected_paths),* ]
             }
         }
+    })
+}
 
-        __tauri_helper_generated::__tauri_collected_handler()
+/// Generates the Tauri generate_handler![] macro invocation with a list of all collected commands.
+///
+/// `tauri_collect_commands!(pub)` is used as an item instead, it only defines the
+/// `__tauri_helper_generated` module and its `__tauri_collected_handler()` function so that the
+/// handler can be re-exported and called from another module:
+///
+/// ```rust,ignore
+/// tauri_collect_commands!(pub);
+/// pub use __tauri_helper_generated::__tauri_collected_handler as handler;
+/// ```
+///
+/// `tauri_collect_commands!(inject_ping)`, which can be combined with `pub`, also registers a
+/// built-in `__tauri_helper_ping` command answering with the version of the macros, to check that
+/// the handler is wired up independently of the commands of the app:
+///
+/// ```js
+/// await invoke("__tauri_helper_ping"); // the version, e.g. "0.1.4"
+/// ```
+#[proc_macro]
+pub fn tauri_collect_commands(item: TokenStream) -> TokenStream {
+    let mut public = false;
+    let mut ping = false;
+    let parser = Punctuated::<proc_macro2::TokenTree, Token![,]>::parse_terminated;
+    let args = match syn::parse::Parser::parse(parser, item) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
     };
+    for arg in args {
+        match arg.to_string().as_str() {
+            "pub" => public = true,
+            "inject_ping" => ping = true,
+            _ => {
+                return syn::Error::new(arg.span(), "expected `pub` or `inject_ping`")
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
 
-    expanded.into()
+    let commands = collect_commands(calling_crate());
+
+    if !public {
+        return tauri_handler(commands.iter(), ping);
+    }
+    handler_module(commands.iter(), ping)
+        .unwrap_or_else(|| {
+            quote! {
+                #[doc(hidden)]
+                pub mod __tauri_helper_generated {
+                    pub fn __tauri_collected_handler() -> tauri::InvokeHandler {
+                        tauri::generate_handler![]
+                    }
+                }
+            }
+        })
+        .into()
+}
+
+/// Same as `tauri_collect_commands!` but hands the commands that aren't registered to a fallback
+/// instead of rejecting them, e.g. to answer unknown commands with a custom error or forward them.
+///
+/// The fallback is called like the handler, with the `tauri::ipc::Invoke` of the command, and
+/// returns whether it handled it.
+///
+/// ```rust,ignore
+/// fn unknown_command<R: tauri::Runtime>(invoke: tauri::ipc::Invoke<R>) -> bool {
+///     let command = invoke.message.command().to_string();
+///     invoke.resolver.reject(format!("unknown command `{}`", command));
+///     true
+/// }
+///
+/// tauri::Builder::default()
+///     .invoke_handler(tauri_collect_commands_with_fallback!(unknown_command))
+/// ```
+#[proc_macro]
+pub fn tauri_collect_commands_with_fallback(item: TokenStream) -> TokenStream {
+    let fallback = parse_macro_input!(item as syn::Expr);
+
+    let commands = collect_commands(calling_crate());
+
+    // the names the handler registers, under the same cfg as the commands
+    let names = commands.iter().flat_map(|command| {
+        let cfg = cfg_attributes(command);
+        std::iter::once(command.exposed_name())
+            .chain(command.aliases.iter().map(String::as_str))
+            .map(move |name| quote! { #(#cfg)* #name })
+    });
+    let handler = proc_macro2::TokenStream::from(tauri_handler(commands.iter(), false));
+
+    // the handler consumes the invocation, the command is checked before dispatching it
+    quote! {
+        {
+            let __tauri_helper_handler = #handler;
+            let __tauri_helper_fallback = #fallback;
+            move |__tauri_helper_invoke: tauri::ipc::Invoke<_>| -> bool {
+                let __tauri_helper_registered: &[&str] = &[ #(#names),* ];
+                if __tauri_helper_registered.contains(&__tauri_helper_invoke.message.command()) {
+                    __tauri_helper_handler(__tauri_helper_invoke)
+                } else {
+                    __tauri_helper_fallback(__tauri_helper_invoke)
+                }
+            }
+        }
+    }
+    .into()
+}
+
+/// Generates a module of wrapper functions calling every collected command, named after the name it
+/// is registered under, so that Rust code such as integration tests can call commands as typed
+/// functions instead of going through `invoke` with a string.
+///
+/// The module is named `invoke` unless another name is given. The wrappers have the signature of
+/// their command, injected arguments such as `State` included, and `async` commands give `async`
+/// wrappers.
+///
+/// ```rust,ignore
+/// tauri_invoke_wrappers!(commands);
+///
+/// #[test]
+/// fn greets() {
+///     assert_eq!(commands::greet("Alice".to_string()), "Hello, Alice!");
+/// }
+/// ```
+///
+/// The types of the signatures are written as in the source of the commands and resolved where the
+/// macro is invoked, which must be at the level of a module, so the types the commands use must be
+/// in scope there. Commands of different crates registered under the same name get wrappers
+/// prefixed with their crate, e.g. `my_app_greet` and `my_plugin_greet`. Commands whose signature
+/// wasn't recorded, such as with the fast scan, are re-exported under their name instead.
+#[proc_macro]
+pub fn tauri_invoke_wrappers(item: TokenStream) -> TokenStream {
+    let module = parse_macro_input!(item as Option<syn::Ident>)
+        .unwrap_or_else(|| syn::Ident::new("invoke", Span::call_site()));
+
+    // paths are kept absolute, the module can't refer to them relatively
+    let current_crate = calling_crate().replace('-', "_");
+    // sorted like the commands of the other macros, the expansion doesn't depend on the order of
+    // the command files and a command listed twice is only wrapped once
+    let commands: BTreeSet<CommandInfo> = collect_commands_with_full_paths().into_iter().collect();
+    let mut crates_by_name: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for command in &commands {
+        let krate = command.path.split("::").next().unwrap_or_default();
+        crates_by_name
+            .entry(command.exposed_name())
+            .or_default()
+            .insert(krate);
+    }
+
+    let wrappers = commands.iter().map(|command| {
+        let (krate, _) = command.path.split_once("::").unwrap_or_default();
+        let path = if krate == current_crate {
+            format!("crate::{}", command.path_in_crate())
+        } else {
+            format!("::{}", command.path)
+        };
+        let path: syn::Path = syn::parse_str(&path)
+            .unwrap_or_else(|e| panic!("Invalid command path `{}`: {}", command.path, e));
+        let name = match crates_by_name[command.exposed_name()].len() {
+            1 => command.exposed_name().to_string(),
+            _ => format!("{}_{}", krate, command.exposed_name()),
+        };
+        let name = syn::Ident::new(&name, Span::call_site());
+        let cfg = cfg_attributes(command);
+
+        match &command.signature {
+            Some(signature) => {
+                let wrapper = invoke_wrapper(command, signature, name, &path);
+                quote! { #(#cfg)* #wrapper }
+            }
+            None => quote! { #(#cfg)* pub use #path as #name; },
+        }
+    });
+
+    quote! {
+        #[allow(unused_imports, clippy::too_many_arguments)]
+        pub mod #module {
+            use super::*;
+
+            #(#wrappers)*
+        }
+    }
+    .into()
+}
+
+/// Builds a function named `name` with the given signature, forwarding its arguments to the
+/// command at `path`.
+fn invoke_wrapper(
+    command: &CommandInfo,
+    signature: &str,
+    name: syn::Ident,
+    path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let mut signature: syn::Signature = syn::parse_str(signature).unwrap_or_else(|e| {
+        panic!(
+            "Invalid signature `{}` of `{}`: {}",
+            signature, command.path, e
+        )
+    });
+    signature.ident = name;
+
+    // patterns such as `State(state): State<..>` are bound to a name to be forwarded
+    let mut args = Vec::new();
+    for (i, input) in signature.inputs.iter_mut().enumerate() {
+        let syn::FnArg::Typed(arg) = input else {
+            continue;
+        };
+        let ident = match &*arg.pat {
+            syn::Pat::Ident(pat) => pat.ident.clone(),
+            _ => format_ident!("__arg{}", i),
+        };
+        arg.attrs.clear();
+        *arg.pat = syn::parse_quote!(#ident);
+        args.push(ident);
+    }
+
+    let call = quote! { #path(#(#args),*) };
+    let body = match signature.asyncness {
+        Some(_) => quote! { #call.await },
+        None => call,
+    };
+    quote! {
+        pub #signature {
+            #body
+        }
+    }
+}
+
+/// Generates the Tauri generate_handler![] macro invocation with the commands available on a platform.
+///
+/// Commands annotated with `#[auto_collect_command(platform = "...")]` are only included when the
+/// platform matches, commands without a platform are included for every platform.
+///
+/// ```rust,ignore
+/// #[cfg(mobile)]
+/// let handler = tauri_collect_commands_for_platform!("mobile");
+/// #[cfg(desktop)]
+/// let handler = tauri_collect_commands_for_platform!("desktop");
+/// ```
+#[proc_macro]
+pub fn tauri_collect_commands_for_platform(item: TokenStream) -> TokenStream {
+    let platform = parse_macro_input!(item as LitStr).value();
+
+    let commands = collect_commands(calling_crate());
+
+    tauri_handler(
+        commands.iter().filter(|c| c.matches_platform(&platform)),
+        false,
+    )
 }
 
 /// Generates an array of command names
@@ -280,15 +814,24 @@ pub fn array_collect_commands(item: TokenStream) -> TokenStream {
 
     let should_print = print_arg.map(|lit| lit.value()).unwrap_or(false);
 
-    let calling_crate = env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "unknown".to_string());
-    let commands = collect_commands(calling_crate);
+    let commands = collect_commands(calling_crate());
 
     if commands.is_empty() {
         return quote! { [] }.into();
     }
 
-    let collected = commands.iter().map(|fn_name| format!("\"{}\"", fn_name));
-    let collected_str = collected.collect::<Vec<_>>().join(", ");
+    // the `crate::` prefix only matters to resolve the paths, it isn't part of the names
+    // one literal per command under its cfg, the same commands the handler registers
+    let collected = commands.iter().map(|command| {
+        let path = command
+            .path
+            .strip_prefix("crate::")
+            .unwrap_or(&command.path);
+        let path = LitStr::new(path, Span::call_site());
+        let cfg = cfg_attributes(command);
+        quote! { #(#cfg)* #path }
+    });
+    let collected_str = quote! { #(#collected),* };
 
     let output = if should_print {
         quote! {
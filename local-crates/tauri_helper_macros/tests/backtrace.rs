@@ -0,0 +1,79 @@
+#![cfg(feature = "backtrace")]
+
+use std::sync::{Arc, Mutex};
+use tauri_helper_macros::WithLogging;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records the names of the fields of every event.
+#[derive(Clone, Default)]
+struct FieldNames(Arc<Mutex<Vec<Vec<String>>>>);
+
+impl Visit for FieldNames {
+    fn record_debug(&mut self, field: &Field, _: &dyn std::fmt::Debug) {
+        let mut events = self.0.lock().unwrap();
+        events.last_mut().unwrap().push(field.name().to_string());
+    }
+}
+
+impl Subscriber for FieldNames {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.0.lock().unwrap().push(Vec::new());
+        event.record(&mut self.clone());
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Returns the field names of the events logged by `f`.
+fn logged_fields(f: impl FnOnce()) -> Vec<Vec<String>> {
+    let subscriber = FieldNames::default();
+    tracing::subscriber::with_default(subscriber.clone(), f);
+    subscriber.0.lock().unwrap().clone()
+}
+
+#[derive(Debug, WithLogging)]
+#[logging(backtrace)]
+enum CapturedError {
+    #[logging_from(String)]
+    Message(String),
+}
+
+#[derive(Debug, WithLogging)]
+enum PlainError {
+    #[logging_from(String)]
+    Message(String),
+}
+
+#[test]
+fn conversions_record_a_backtrace_when_enabled() {
+    let fields = logged_fields(|| {
+        let CapturedError::Message(message) = CapturedError::from("oops".to_string());
+        assert_eq!(message, "oops");
+    });
+    assert_eq!(fields, [["message", "backtrace"]]);
+}
+
+#[test]
+fn conversions_record_no_backtrace_by_default() {
+    let fields = logged_fields(|| {
+        let PlainError::Message(message) = PlainError::from("oops".to_string());
+        assert_eq!(message, "oops");
+    });
+    assert_eq!(fields, [["message"]]);
+}
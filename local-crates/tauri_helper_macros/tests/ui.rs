@@ -0,0 +1,6 @@
+#![cfg(feature = "tracing")]
+
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use tauri_helper_macros::WithLogging;
+
+#[derive(WithLogging)]
+enum Error {
+    #[logging_from(String)]
+    Unknown,
+}
+
+fn main() {}
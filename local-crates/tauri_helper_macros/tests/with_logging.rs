@@ -0,0 +1,145 @@
+#![cfg(feature = "tracing")]
+
+use tauri_helper_macros::WithLogging;
+
+#[derive(Debug, PartialEq, WithLogging)]
+#[repr(u8)]
+enum DiscriminantError {
+    Unknown = 1,
+
+    #[logging_from(String)]
+    Message(String) = 5,
+
+    Timeout = 7,
+
+    #[logging_from(i32)]
+    Code(i32),
+}
+
+impl DiscriminantError {
+    /// Reads the discriminant, `repr(u8)` enums start with it.
+    fn discriminant(&self) -> u8 {
+        // SAFETY: a `repr(u8)` enum is laid out as a `repr(C)` union of `repr(C)` structs each
+        // starting with the `u8` discriminant
+        unsafe { *(self as *const Self as *const u8) }
+    }
+}
+
+#[test]
+fn discriminants_are_kept_and_ignored_by_the_conversions() {
+    assert_eq!(
+        DiscriminantError::from("oops".to_string()),
+        DiscriminantError::Message("oops".to_string())
+    );
+    assert_eq!(DiscriminantError::from(3), DiscriminantError::Code(3));
+
+    assert_eq!(DiscriminantError::Unknown.discriminant(), 1);
+    assert_eq!(DiscriminantError::Message(String::new()).discriminant(), 5);
+    assert_eq!(DiscriminantError::Timeout.discriminant(), 7);
+    assert_eq!(DiscriminantError::Code(0).discriminant(), 8);
+}
+
+#[derive(Debug, PartialEq, WithLogging)]
+#[non_exhaustive]
+enum NonExhaustiveError {
+    #[logging_from(String)]
+    Message(String),
+
+    Context {
+        code: i32,
+        message: String,
+    },
+
+    Unknown,
+}
+
+#[test]
+fn non_exhaustive_enums_are_converted() {
+    assert_eq!(
+        NonExhaustiveError::from("oops".to_string()),
+        NonExhaustiveError::Message("oops".to_string())
+    );
+    assert_eq!(
+        NonExhaustiveError::from((2, "denied".to_string())),
+        NonExhaustiveError::Context {
+            code: 2,
+            message: "denied".to_string()
+        }
+    );
+    assert_ne!(
+        NonExhaustiveError::Unknown,
+        NonExhaustiveError::from(String::new())
+    );
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Reason(String);
+
+impl From<&'static str> for Reason {
+    fn from(value: &'static str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl std::fmt::Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, WithLogging)]
+enum BoundedError<T>
+where
+    T: Clone,
+{
+    #[logging_from(&'static str)]
+    Message(T),
+
+    Context(T, u32),
+}
+
+#[test]
+fn bounded_generic_enums_are_converted() {
+    assert_eq!(
+        BoundedError::<Reason>::from("denied"),
+        BoundedError::Message(Reason("denied".to_string()))
+    );
+    assert_eq!(
+        BoundedError::from((Reason("denied".to_string()), 3)),
+        BoundedError::Context(Reason("denied".to_string()), 3)
+    );
+}
+
+/// Implements `Debug` but not `Display`.
+#[derive(Debug, PartialEq)]
+struct Status {
+    code: u16,
+}
+
+impl From<u16> for Status {
+    fn from(code: u16) -> Self {
+        Self { code }
+    }
+}
+
+#[derive(Debug, PartialEq, WithLogging)]
+enum DebugOnlyError {
+    #[logging(debug)]
+    #[logging_from(u16)]
+    Status(Status),
+
+    #[logging_from(String)]
+    Message(String),
+}
+
+#[test]
+fn debug_only_fields_are_converted() {
+    assert_eq!(
+        DebugOnlyError::from(404),
+        DebugOnlyError::Status(Status { code: 404 })
+    );
+    assert_eq!(
+        DebugOnlyError::from("oops".to_string()),
+        DebugOnlyError::Message("oops".to_string())
+    );
+}
@@ -0,0 +1,60 @@
+//! `cargo tauri-helper`, inspects the commands of the workspace from the command line.
+//!
+//! ```text
+//! cargo tauri-helper list [--json] [--collect-all]
+//! ```
+
+use std::env;
+use std::process;
+use tauri_helper::{TauriHelperOptions, scan_workspace_at};
+use tauri_helper_core::find_workspace_dir;
+
+const USAGE: &str = "Usage: cargo tauri-helper list [--json] [--collect-all]";
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // cargo passes the name of the subcommand as the first argument
+    if args.first().is_some_and(|arg| arg == "tauri-helper") {
+        args.remove(0);
+    }
+
+    match args.first().map(String::as_str) {
+        Some("list") => list(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Prints the commands of the workspace containing the current directory.
+fn list(args: &[String]) {
+    let mut json = false;
+    let mut options = TauriHelperOptions::default();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--collect-all" => options.collect_all = true,
+            other => {
+                eprintln!("Unknown argument `{}`\n{}", other, USAGE);
+                process::exit(2);
+            }
+        }
+    }
+
+    let workspace_root = find_workspace_dir(&env::current_dir().unwrap());
+    let scan = scan_workspace_at(&workspace_root, &options);
+
+    // warnings go to stderr so that the output can be piped
+    for warning in scan.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&scan.to_json()).unwrap());
+    } else {
+        for command in scan.commands() {
+            println!("{}", command.path);
+        }
+    }
+}
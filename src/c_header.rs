@@ -0,0 +1,100 @@
+use tauri_helper_core::command::CommandInfo;
+
+/// Generates a C header defining a constant per command and an array of every command name.
+pub(crate) fn generate_c_header(commands: &[CommandInfo]) -> String {
+    let mut names = commands
+        .iter()
+        .map(|command| command.exposed_name())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    let mut out = String::from(
+        "/* Generated by tauri-helper, do not edit. */
+#ifndef TAURI_COMMANDS_H
+#define TAURI_COMMANDS_H
+
+#include <stddef.h>
+
+",
+    );
+
+    for name in &names {
+        out.push_str(&format!("#define {} \"{}\"\n", define_name(name), name));
+    }
+
+    out.push_str(&format!(
+        "\n#define TAURI_COMMANDS_COUNT {}\n\nstatic const char* TAURI_COMMANDS[] = {{\n",
+        names.len()
+    ));
+    for name in &names {
+        out.push_str(&format!("    {},\n", define_name(name)));
+    }
+    out.push_str("    NULL,\n};\n\n#endif /* TAURI_COMMANDS_H */\n");
+
+    out
+}
+
+/// Returns the name of the define holding a command name, e.g. `TAURI_COMMAND_GREET`.
+fn define_name(name: &str) -> String {
+    let name = name
+        .trim_start_matches("r#")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("TAURI_COMMAND_{}", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn header_defines_every_command() {
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn set_theme() {}\n",
+            )
+            .file(
+                "users/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n\n\
+                #[tauri::command]\n#[auto_collect_command]\npub fn r#move() {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let commands: Vec<CommandInfo> = scan.commands().cloned().collect();
+        assert_eq!(
+            generate_c_header(&commands),
+            r#"/* Generated by tauri-helper, do not edit. */
+#ifndef TAURI_COMMANDS_H
+#define TAURI_COMMANDS_H
+
+#include <stddef.h>
+
+#define TAURI_COMMAND_GREET "greet"
+#define TAURI_COMMAND_MOVE "move"
+#define TAURI_COMMAND_SET_THEME "set_theme"
+
+#define TAURI_COMMANDS_COUNT 3
+
+static const char* TAURI_COMMANDS[] = {
+    TAURI_COMMAND_GREET,
+    TAURI_COMMAND_MOVE,
+    TAURI_COMMAND_SET_THEME,
+    NULL,
+};
+
+#endif /* TAURI_COMMANDS_H */
+"#
+        );
+    }
+}
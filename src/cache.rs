@@ -0,0 +1,442 @@
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri_helper_core::args::CommandArgs;
+use tauri_helper_core::types::TauriHelperOptions;
+use tauri_helper_core::workspace_relative;
+
+use crate::lockfile::fnv1a;
+use crate::modules::ModuleInfo;
+use crate::scan::{FileScan, OutDirInclude, ScannedCommand};
+use crate::signature::{CommandArg, CommandSignature, RustType, StructDef, StructField};
+
+/// Results of the previous scans, keyed by a hash of the content of each file, see
+/// `TauriHelperOptions::scan_cache`.
+///
+/// Modification times are never looked at: they change on every checkout, so that a CI job
+/// restoring the cache would parse everything again, and some filesystems don't update them
+/// reliably. A file is parsed again when its content, the module it is loaded as or the options
+/// affecting the scan change.
+pub(crate) struct ScanCache {
+    workspace_root: PathBuf,
+    /// Entries read from the cache file, keyed by the path of the file relative to the workspace.
+    previous: HashMap<String, (String, Value)>,
+    /// Entries of the current scan, the ones of files that are no longer scanned are dropped.
+    current: Mutex<Vec<(String, String, Value)>>,
+    hits: AtomicUsize,
+}
+
+impl ScanCache {
+    /// Reads the cache file, a missing or unreadable one gives an empty cache.
+    pub(crate) fn load(workspace_root: &Path, cache_file: &Path) -> Self {
+        let mut previous = HashMap::new();
+        if let Ok(contents) = fs::read_to_string(cache_file)
+            && let Ok(json) = serde_json::from_str::<Value>(&contents)
+            && json.get("version").and_then(Value::as_str) == Some(env!("CARGO_PKG_VERSION"))
+            && let Some(files) = json.get("files").and_then(Value::as_object)
+        {
+            for (path, entry) in files {
+                if let (Some(key), Some(scan)) =
+                    (entry.get("key").and_then(Value::as_str), entry.get("scan"))
+                {
+                    previous.insert(path.clone(), (key.to_string(), scan.clone()));
+                }
+            }
+        }
+
+        Self {
+            workspace_root: workspace_root.to_path_buf(),
+            previous,
+            current: Mutex::new(Vec::new()),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the scan of a file from the cache if its key matches, running `scan` otherwise.
+    pub(crate) fn get_or_scan(
+        &self,
+        path: &Path,
+        content: &str,
+        module: &ModuleInfo,
+        options: &TauriHelperOptions,
+        scan: impl FnOnce() -> FileScan,
+    ) -> FileScan {
+        let relative = workspace_relative(&self.workspace_root, path);
+        let key = cache_key(&relative, content, module, options);
+        // the entries hold the path of the file relative to the workspace, so that they can be
+        // restored into another checkout
+        let location = Location {
+            absolute: path.display().to_string(),
+            relative: relative.clone(),
+        };
+
+        let cached = self
+            .previous
+            .get(&relative)
+            .filter(|(previous_key, _)| *previous_key == key)
+            .and_then(|(_, json)| Some((file_scan_from_json(json, &location)?, json.clone())));
+        let (file_scan, json) = match cached {
+            Some(cached) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                cached
+            }
+            None => {
+                let file_scan = scan();
+                let json = file_scan_to_json(&file_scan, &location);
+                (file_scan, json)
+            }
+        };
+
+        self.current.lock().unwrap().push((relative, key, json));
+        file_scan
+    }
+
+    /// Number of files whose scan was read from the cache.
+    pub(crate) fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Writes the entries of the current scan to the cache file.
+    pub(crate) fn save(self, cache_file: &Path) {
+        let mut entries = self.current.into_inner().unwrap();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let files: serde_json::Map<String, Value> = entries
+            .into_iter()
+            .map(|(path, key, scan)| (path, json!({ "key": key, "scan": scan })))
+            .collect();
+        let json = json!({ "version": env!("CARGO_PKG_VERSION"), "files": files });
+
+        if let Some(parent) = cache_file.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        // a cache that can't be written only makes the next build slower
+        let _ = fs::write(cache_file, json.to_string());
+    }
+}
+
+/// The path of a scanned file, written relative to the workspace in the cache entries.
+struct Location {
+    absolute: String,
+    relative: String,
+}
+
+impl Location {
+    /// Replaces the path of the file in a message of the scan with its relative path.
+    fn to_relative(&self, message: &str) -> String {
+        message.replace(&self.absolute, &self.relative)
+    }
+
+    /// Replaces the relative path written by [`to_relative`](Self::to_relative) with the path of
+    /// the file in the current workspace.
+    fn to_absolute(&self, message: &str) -> String {
+        message.replace(&self.relative, &self.absolute)
+    }
+}
+
+/// Hashes the content of a file along with everything else its scan depends on.
+fn cache_key(
+    relative_path: &str,
+    content: &str,
+    module: &ModuleInfo,
+    options: &TauriHelperOptions,
+) -> String {
+    let context = format!(
+        "{}\0{:?}\0{:?}\0{}\0{:?}\0{}\0{}\0{}\0{}\0{}\0{}\0",
+        relative_path,
+        module.path,
+        module.cfg,
+        options.marker_attribute,
+        options.marker_aliases,
+        options.collect_all,
+        options.require_annotation,
+        options.include_private,
+        options.include_test_commands,
+        options.fast_scan,
+        options.return_schemas.is_some(),
+    );
+    format!("{:016x}", fnv1a(context.bytes().chain(content.bytes())))
+}
+
+fn strings(json: Option<&Value>) -> Option<Vec<String>> {
+    json?
+        .as_array()?
+        .iter()
+        .map(|value| value.as_str().map(str::to_string))
+        .collect()
+}
+
+fn optional_string(json: Option<&Value>) -> Option<String> {
+    json.and_then(Value::as_str).map(str::to_string)
+}
+
+fn file_scan_to_json(scan: &FileScan, location: &Location) -> Value {
+    let relative = |messages: &[String]| -> Vec<String> {
+        messages.iter().map(|m| location.to_relative(m)).collect()
+    };
+    json!({
+        "commands": scan.commands.iter().map(command_to_json).collect::<Vec<_>>(),
+        "warnings": relative(&scan.warnings),
+        "unannotated": relative(&scan.unannotated),
+        "includes": scan
+            .includes
+            .iter()
+            .map(|include| {
+                json!({
+                    "file": include.file,
+                    "module": include.module.path,
+                    "cfg": include.module.cfg,
+                    "from": location.to_relative(&include.from.to_string_lossy()),
+                })
+            })
+            .collect::<Vec<_>>(),
+        "structs": scan.structs.iter().map(struct_to_json).collect::<Vec<_>>(),
+    })
+}
+
+fn file_scan_from_json(json: &Value, location: &Location) -> Option<FileScan> {
+    let absolute = |messages: Vec<String>| -> Vec<String> {
+        messages.iter().map(|m| location.to_absolute(m)).collect()
+    };
+    Some(FileScan {
+        commands: json
+            .get("commands")?
+            .as_array()?
+            .iter()
+            .map(command_from_json)
+            .collect::<Option<_>>()?,
+        warnings: absolute(strings(json.get("warnings"))?),
+        unannotated: absolute(strings(json.get("unannotated"))?),
+        includes: json
+            .get("includes")?
+            .as_array()?
+            .iter()
+            .map(|include| {
+                Some(OutDirInclude {
+                    file: include.get("file")?.as_str()?.to_string(),
+                    module: ModuleInfo {
+                        path: strings(include.get("module"))?,
+                        test_only: false,
+                        cfg: strings(include.get("cfg"))?,
+                    },
+                    from: PathBuf::from(location.to_absolute(include.get("from")?.as_str()?)),
+                })
+            })
+            .collect::<Option<_>>()?,
+        structs: json
+            .get("structs")?
+            .as_array()?
+            .iter()
+            .map(struct_from_json)
+            .collect::<Option<_>>()?,
+    })
+}
+
+fn command_to_json(command: &ScannedCommand) -> Value {
+    let args = &command.args;
+    json!({
+        "name": command.name,
+        "module": command.module,
+        "cfg": command.cfg,
+        "platform": args.platform,
+        "group": args.group,
+        "rename": args.rename,
+        "aliases": args.aliases,
+        "timeout_ms": args.timeout_ms,
+        "args": command
+            .signature
+            .args
+            .iter()
+            .map(|arg| json!({ "name": arg.name, "ty": type_to_json(&arg.ty) }))
+            .collect::<Vec<_>>(),
+        "output": command.signature.output.as_ref().map(type_to_json),
+        "tokens": command.signature.tokens,
+        "signature_hash": command.signature_hash,
+        "public": command.public,
+    })
+}
+
+fn command_from_json(json: &Value) -> Option<ScannedCommand> {
+    let output = match json.get("output")? {
+        Value::Null => None,
+        output => Some(type_from_json(output)?),
+    };
+    Some(ScannedCommand {
+        name: json.get("name")?.as_str()?.to_string(),
+        module: strings(json.get("module"))?,
+        cfg: strings(json.get("cfg"))?,
+        args: CommandArgs {
+            platform: optional_string(json.get("platform")),
+            group: optional_string(json.get("group")),
+            rename: optional_string(json.get("rename")),
+            aliases: strings(json.get("aliases"))?,
+            timeout_ms: json.get("timeout_ms").and_then(Value::as_u64),
+        },
+        signature: CommandSignature {
+            args: json
+                .get("args")?
+                .as_array()?
+                .iter()
+                .map(|arg| {
+                    Some(CommandArg {
+                        name: arg.get("name")?.as_str()?.to_string(),
+                        ty: type_from_json(arg.get("ty")?)?,
+                    })
+                })
+                .collect::<Option<_>>()?,
+            output,
+            tokens: optional_string(json.get("tokens")),
+        },
+        signature_hash: json.get("signature_hash")?.as_str()?.to_string(),
+        public: json.get("public")?.as_bool()?,
+    })
+}
+
+fn struct_to_json(def: &StructDef) -> Value {
+    json!({
+        "name": def.name,
+        "fields": def
+            .fields
+            .iter()
+            .map(|field| json!({ "name": field.name, "ty": type_to_json(&field.ty) }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn struct_from_json(json: &Value) -> Option<StructDef> {
+    Some(StructDef {
+        name: json.get("name")?.as_str()?.to_string(),
+        fields: json
+            .get("fields")?
+            .as_array()?
+            .iter()
+            .map(|field| {
+                Some(StructField {
+                    name: field.get("name")?.as_str()?.to_string(),
+                    ty: type_from_json(field.get("ty")?)?,
+                })
+            })
+            .collect::<Option<_>>()?,
+    })
+}
+
+/// Encodes a type as a single-entry object keyed by its kind, e.g. `{ "ref": { "path": "str" } }`.
+fn type_to_json(ty: &RustType) -> Value {
+    let types = |types: &[RustType]| types.iter().map(type_to_json).collect::<Vec<_>>();
+    match ty {
+        RustType::Path { name, generics } => json!({ "path": name, "generics": types(generics) }),
+        RustType::Reference(inner) => json!({ "ref": type_to_json(inner) }),
+        RustType::Slice(inner) => json!({ "slice": type_to_json(inner) }),
+        RustType::Array(inner) => json!({ "array": type_to_json(inner) }),
+        RustType::Tuple(items) => json!({ "tuple": types(items) }),
+        RustType::Other(ty) => json!({ "other": ty }),
+    }
+}
+
+fn type_from_json(json: &Value) -> Option<RustType> {
+    let boxed = |key: &str| Some(Box::new(type_from_json(json.get(key)?)?));
+    let types = |key: &str| -> Option<Vec<RustType>> {
+        json.get(key)?
+            .as_array()?
+            .iter()
+            .map(type_from_json)
+            .collect()
+    };
+    let (kind, _) = json.as_object()?.iter().next()?;
+    Some(match kind.as_str() {
+        "path" => RustType::Path {
+            name: json.get("path")?.as_str()?.to_string(),
+            generics: types("generics")?,
+        },
+        "ref" => RustType::Reference(boxed("ref")?),
+        "slice" => RustType::Slice(boxed("slice")?),
+        "array" => RustType::Array(boxed("array")?),
+        "tuple" => RustType::Tuple(types("tuple")?),
+        "other" => RustType::Other(json.get("other")?.as_str()?.to_string()),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn files_whose_content_is_unchanged_are_read_from_the_cache() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "pub mod users;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/users.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            );
+        let options = TauriHelperOptions::builder()
+            .scan_cache("target/tauri-helper-cache.json")
+            .build();
+
+        let scan = fixture.scan(&options);
+        assert_eq!(scan.files_cached, 0);
+
+        // as after a fresh checkout, the files are newer than the cache
+        for file in ["cmds/src/lib.rs", "cmds/src/users.rs"] {
+            fs::File::options()
+                .write(true)
+                .open(fixture.root().join(file))
+                .unwrap()
+                .set_modified(SystemTime::now() + Duration::from_secs(3600))
+                .unwrap();
+        }
+        let scan = fixture.scan(&options);
+        assert_eq!(scan.files_cached, 2);
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["cmds::greet", "cmds::users::load_user"]
+        );
+
+        let fixture = fixture.file(
+            "cmds/src/users.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn save_user() {}\n",
+        );
+        let scan = fixture.scan(&options);
+        assert_eq!(scan.files_cached, 1);
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["cmds::greet", "cmds::users::save_user"]
+        );
+    }
+
+    #[test]
+    fn caches_restored_into_another_checkout_are_used() {
+        let checkout = || {
+            Fixture::workspace(&["cmds"]).file(
+                "cmds/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n\n\
+                #[tauri::command]\n#[auto_collect_command]\npub fn parse<T>() {}\n",
+            )
+        };
+        let options = TauriHelperOptions::builder()
+            .scan_cache("target/tauri-helper-cache.json")
+            .build();
+        let first = checkout();
+        first.scan(&options);
+
+        let cache = fs::read_to_string(first.root().join("target/tauri-helper-cache.json"));
+        let second = checkout().file("target/tauri-helper-cache.json", &cache.unwrap());
+        let scan = second.scan(&options);
+        assert_eq!(scan.files_cached, 1);
+        assert_eq!(second.command_paths(&options), ["cmds::greet"]);
+        // the warnings name the files of the checkout they are raised in
+        let warnings: Vec<&str> = scan.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(&format!(
+            "at {}:7 ",
+            second.root().join("cmds/src/lib.rs").display()
+        )));
+    }
+}
@@ -0,0 +1,179 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use tauri_helper_core::command::CommandInfo;
+
+/// Differences between two command manifests, see [`diff_manifests`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    /// Commands only found in the new manifest.
+    pub added: Vec<CommandInfo>,
+    /// Commands only found in the old manifest.
+    pub removed: Vec<CommandInfo>,
+    /// Commands exposed under another name, as `(old, new)`.
+    pub renamed: Vec<(CommandInfo, CommandInfo)>,
+}
+
+impl ManifestDiff {
+    /// Returns `true` if the manifests expose the same commands under the same names.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty()
+    }
+
+    /// Returns `true` if a frontend written against the old manifest may break, i.e. if a command
+    /// was removed or renamed.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.renamed.is_empty()
+    }
+}
+
+impl fmt::Display for ManifestDiff {
+    /// Writes one changelog line per difference.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for command in &self.added {
+            writeln!(f, "added `{}` ({})", command.exposed_name(), command.path)?;
+        }
+        for command in &self.removed {
+            writeln!(f, "removed `{}` ({})", command.exposed_name(), command.path)?;
+        }
+        for (old, new) in &self.renamed {
+            writeln!(
+                f,
+                "renamed `{}` to `{}` ({})",
+                old.exposed_name(),
+                new.exposed_name(),
+                new.path
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two command manifests, e.g. the `_all.json` of two releases, reporting the commands
+/// added, removed and renamed between them.
+///
+/// The manifests are in the `{ "crates": { "<crate>": [<command>, ...] } }` format written in the
+/// commands directory and by `cargo tauri-helper list --json`. Commands are matched by path, a
+/// command exposed under another name is renamed.
+///
+/// ```rust,ignore
+/// let diff = tauri_helper::diff_manifests(Path::new("v1/_all.json"), Path::new("v2/_all.json"));
+/// if diff.is_breaking() {
+///     println!("breaking changes:\n{}", diff);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a manifest can't be read or isn't a valid manifest.
+pub fn diff_manifests(old: &Path, new: &Path) -> ManifestDiff {
+    let old = read_manifest(old);
+    let new = read_manifest(new);
+
+    let mut diff = ManifestDiff::default();
+    for (path, command) in &new {
+        match old.get(path) {
+            None => diff.added.push(command.clone()),
+            Some(previous) if previous.exposed_name() != command.exposed_name() => {
+                diff.renamed.push((previous.clone(), command.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed = old
+        .into_iter()
+        .filter(|(path, _)| !new.contains_key(path))
+        .map(|(_, command)| command)
+        .collect();
+    diff
+}
+
+/// Reads the commands of a manifest, keyed by path.
+fn read_manifest(path: &Path) -> BTreeMap<String, CommandInfo> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read the manifest {}: {}", path.display(), e));
+    let manifest: Value = serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Invalid manifest {}: {}", path.display(), e));
+    let crates = manifest
+        .get("crates")
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| panic!("Invalid manifest {}: no `crates` object", path.display()));
+
+    crates
+        .values()
+        .flat_map(|commands| commands.as_array().into_iter().flatten())
+        .filter_map(CommandInfo::from_json)
+        .map(|command| (command.path.clone(), command))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+
+    #[test]
+    fn manifests_are_diffed_by_path() {
+        let fixture = Fixture::workspace(&[])
+            .file(
+                "v1.json",
+                r#"{ "crates": {
+                    "app": [
+                        { "path": "app::greet" },
+                        { "path": "app::load_user", "name": "get_user" },
+                        { "path": "app::legacy" }
+                    ]
+                } }"#,
+            )
+            .file(
+                "v2.json",
+                r#"{ "crates": {
+                    "app": [
+                        { "path": "app::greet" },
+                        { "path": "app::load_user", "name": "fetch_user" }
+                    ],
+                    "settings": [{ "path": "settings::set_theme", "group": "settings" }]
+                } }"#,
+            );
+
+        let diff = diff_manifests(
+            &fixture.root().join("v1.json"),
+            &fixture.root().join("v2.json"),
+        );
+        assert_eq!(
+            diff,
+            ManifestDiff {
+                added: vec![CommandInfo {
+                    group: Some("settings".to_string()),
+                    ..CommandInfo::new("settings::set_theme")
+                }],
+                removed: vec![CommandInfo::new("app::legacy")],
+                renamed: vec![(
+                    CommandInfo {
+                        name: Some("get_user".to_string()),
+                        ..CommandInfo::new("app::load_user")
+                    },
+                    CommandInfo {
+                        name: Some("fetch_user".to_string()),
+                        ..CommandInfo::new("app::load_user")
+                    }
+                )],
+            }
+        );
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            "added `set_theme` (settings::set_theme)\n\
+            removed `legacy` (app::legacy)\n\
+            renamed `get_user` to `fetch_user` (app::load_user)\n"
+        );
+
+        let same = diff_manifests(
+            &fixture.root().join("v1.json"),
+            &fixture.root().join("v1.json"),
+        );
+        assert!(same.is_empty());
+    }
+}
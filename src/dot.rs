@@ -0,0 +1,124 @@
+use std::collections::BTreeSet;
+
+use crate::scan::WorkspaceScan;
+
+/// Generates a Graphviz graph linking every crate to the commands it defines, commands in a group
+/// are drawn inside a cluster named after it.
+///
+/// Render it with `dot -Tsvg commands.dot -o commands.svg`.
+pub(crate) fn generate_dot(scan: &WorkspaceScan) -> String {
+    let mut out = String::from(
+        "// Generated by tauri-helper, do not edit.\ndigraph commands {\n  rankdir=LR;\n  node [fontname=\"monospace\"];\n",
+    );
+
+    let crates = scan.crates();
+    let groups: BTreeSet<&str> = scan
+        .commands()
+        .filter_map(|command| command.group.as_deref())
+        .collect();
+
+    out.push('\n');
+    for crate_name in crates.keys() {
+        out.push_str(&format!(
+            "  {} [shape=box, label={}];\n",
+            quote(&format!("crate:{}", crate_name)),
+            quote(crate_name)
+        ));
+    }
+
+    for group in &groups {
+        out.push_str(&format!(
+            "\n  subgraph {} {{\n    label={};\n",
+            quote(&format!("cluster_{}", group)),
+            quote(group)
+        ));
+        for command in scan
+            .commands()
+            .filter(|command| command.group.as_deref() == Some(*group))
+        {
+            out.push_str(&format!(
+                "    {} [label={}];\n",
+                quote(&command.path),
+                quote(command.exposed_name())
+            ));
+        }
+        out.push_str("  }\n");
+    }
+
+    out.push('\n');
+    for (crate_name, commands) in &crates {
+        for command in *commands {
+            if command.group.is_none() {
+                out.push_str(&format!(
+                    "  {} [label={}];\n",
+                    quote(&command.path),
+                    quote(command.exposed_name())
+                ));
+            }
+            out.push_str(&format!(
+                "  {} -> {};\n",
+                quote(&format!("crate:{}", crate_name)),
+                quote(&command.path)
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Quotes a Graphviz identifier.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn graph_has_a_node_per_crate_and_command() {
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "settings")]
+                pub fn set_theme() {}
+                "#,
+            )
+            .file(
+                "users/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(rename = "hello")]
+                pub fn greet() {}
+                "#,
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            generate_dot(&scan),
+            r#"// Generated by tauri-helper, do not edit.
+digraph commands {
+  rankdir=LR;
+  node [fontname="monospace"];
+
+  "crate:settings" [shape=box, label="settings"];
+  "crate:users" [shape=box, label="users"];
+
+  subgraph "cluster_settings" {
+    label="settings";
+    "settings::set_theme" [label="set_theme"];
+  }
+
+  "crate:settings" -> "settings::set_theme";
+  "users::greet" [label="hello"];
+  "crate:users" -> "users::greet";
+}
+"#
+        );
+    }
+}
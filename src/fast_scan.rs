@@ -0,0 +1,239 @@
+use tauri_helper_core::types::TauriHelperOptions;
+
+use crate::modules::ModuleInfo;
+use crate::scan::{FileScan, ScannedCommand};
+use crate::signature::CommandSignature;
+
+/// Finds the commands of a file by matching its text instead of parsing it, see
+/// `TauriHelperOptions::fast_scan`.
+///
+/// Every run of attributes starting a line is looked at, when it holds the marker attribute (or one
+/// of its aliases) the function it is followed by is collected along with the predicates of the
+/// `#[cfg(...)]` attributes of the run. Anything else, such as the arguments of the marker, inline
+/// modules or `#[cfg(test)]` blocks, is not understood.
+pub(crate) fn scan_content(
+    content: &str,
+    path: &std::path::Path,
+    module: &ModuleInfo,
+    options: &TauriHelperOptions,
+) -> FileScan {
+    let mut scan = FileScan::default();
+    let markers: Vec<&str> = std::iter::once(options.marker_attribute.as_str())
+        .chain(options.marker_aliases.iter().map(String::as_str))
+        .collect();
+
+    let mut rest = content;
+    while let Some(start) = find_attribute(rest) {
+        let (attrs, after) = attribute_run(&rest[start..]);
+        rest = after;
+
+        let is_command = attrs.iter().any(|attr| {
+            let name = attr_path(attr);
+            markers
+                .iter()
+                .any(|marker| name == *marker || name.ends_with(&format!("::{}", marker)))
+        });
+        if !is_command {
+            continue;
+        }
+        let Some((name, visibility)) = fn_head(after) else {
+            continue;
+        };
+
+        if !options.include_private && visibility.is_none() {
+            scan.warnings.push(format!(
+                "command `{}` in {} is private and was skipped, make it `pub` or enable `include_private`",
+                name,
+                path.display()
+            ));
+            continue;
+        }
+
+        let mut cfg = module.cfg.clone();
+        cfg.extend(attrs.iter().filter_map(|attr| {
+            let predicate = attr.strip_prefix("cfg")?.trim_start();
+            Some(
+                predicate
+                    .strip_prefix('(')?
+                    .strip_suffix(')')?
+                    .trim()
+                    .to_string(),
+            )
+        }));
+
+        scan.commands.push(ScannedCommand {
+            name: name.to_string(),
+            module: module.path.clone(),
+            cfg,
+            args: Default::default(),
+            signature: CommandSignature::default(),
+            signature_hash: String::new(),
+            public: visibility == Some(Visibility::Public),
+        });
+    }
+    scan
+}
+
+/// Returns the offset of the first `#[` starting a line, attributes in comments are skipped since
+/// the line starts with `//`.
+fn find_attribute(content: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("#[") {
+            return Some(offset + line.len() - trimmed.len());
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Splits the attributes at the start of `content` from what follows them, returning the content
+/// of each attribute, e.g. `cfg(feature = "admin")` for `#[cfg(feature = "admin")]`.
+fn attribute_run(mut content: &str) -> (Vec<&str>, &str) {
+    let mut attrs = Vec::new();
+    loop {
+        content = skip_trivia(content);
+        let Some(attr) = content.strip_prefix("#[") else {
+            break;
+        };
+        let mut depth = 1;
+        let Some(end) = attr.find(|c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        }) else {
+            break;
+        };
+        attrs.push(attr[..end].trim());
+        content = &attr[end + 1..];
+    }
+    (attrs, content)
+}
+
+/// Returns the path of an attribute, e.g. `tauri_helper::auto_collect_command`.
+fn attr_path(attr: &str) -> String {
+    attr.split(['(', '='])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visibility {
+    /// `pub`
+    Public,
+    /// `pub(crate)`, `pub(super)` or `pub(in path)`
+    Restricted,
+}
+
+/// Parses the head of a function, returning its name and its visibility, `None` if the item isn't
+/// a function.
+fn fn_head(content: &str) -> Option<(&str, Option<Visibility>)> {
+    let mut rest = skip_trivia(content);
+    let mut visibility = None;
+    loop {
+        let (word, after) = split_word(rest);
+        rest = after;
+        match word {
+            "pub" => match rest.trim_start().strip_prefix('(') {
+                Some(scope) => {
+                    rest = &scope[scope.find(')')? + 1..];
+                    visibility = Some(Visibility::Restricted);
+                }
+                None => visibility = Some(Visibility::Public),
+            },
+            "async" | "unsafe" | "const" => {}
+            // the ABI of `extern "C" fn`
+            "extern" => {
+                let abi = rest.trim_start().strip_prefix('"')?;
+                rest = &abi[abi.find('"')? + 1..];
+            }
+            "fn" => {
+                let (name, _) = split_word(rest);
+                return (!name.is_empty()).then_some((name, visibility));
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Splits the identifier or keyword at the start of `content`, raw identifiers included.
+fn split_word(content: &str) -> (&str, &str) {
+    let content = content.trim_start();
+    let end = content
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '#'))
+        .unwrap_or(content.len());
+    content.split_at(end)
+}
+
+/// Skips whitespace and line comments.
+fn skip_trivia(mut content: &str) -> &str {
+    loop {
+        content = content.trim_start();
+        match content.strip_prefix("//") {
+            Some(comment) => content = comment.split_once('\n').map_or("", |(_, rest)| rest),
+            None => return content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkspaceScan;
+    use crate::test_support::Fixture;
+
+    #[test]
+    fn fast_scan_finds_the_commands_of_the_full_scan() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            use tauri::State;
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet(name: String) -> String {
+                format!("Hello, {}!", name)
+            }
+
+            // #[auto_collect_command]
+            pub fn commented_out() {}
+
+            #[cfg(feature = "admin")]
+            #[tauri::command]
+            #[auto_collect_command]
+            pub async fn export_logs(state: State<'_, AppState>) -> Result<(), String> {
+                Ok(())
+            }
+
+            #[tauri::command]
+            pub fn unmarked() {}
+            "#,
+        );
+
+        let full = fixture.scan(&TauriHelperOptions::default());
+        let fast = fixture.scan(&TauriHelperOptions::builder().fast_scan(true).build());
+        let commands = |scan: &WorkspaceScan| -> Vec<(String, Vec<String>)> {
+            scan.commands()
+                .map(|command| (command.path.clone(), command.cfg.clone()))
+                .collect()
+        };
+        assert_eq!(commands(&fast), commands(&full));
+        assert_eq!(
+            commands(&full),
+            [
+                ("cmds::greet".to_string(), Vec::new()),
+                (
+                    "cmds::export_logs".to_string(),
+                    vec!["feature = \"admin\"".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use tauri_helper_core::command::CommandInfo;
+
+use crate::scan::WorkspaceScan;
+
+/// Generates a JSON object describing every command, keyed by the name it is exposed under:
+///
+/// ```json
+/// {
+///   "greet": {
+///     "rust_path": "my_app::greet",
+///     "crate": "my_app",
+///     "group": "users"
+///   }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if two commands are exposed under the same name, they couldn't both be looked up.
+pub(crate) fn generate_keyed_json(scan: &WorkspaceScan) -> String {
+    let mut entries: BTreeMap<&str, (&str, &CommandInfo)> = BTreeMap::new();
+    // every name a command is registered under, aliases included
+    let mut names: BTreeMap<&str, &str> = BTreeMap::new();
+
+    for (crate_name, commands) in scan.crates() {
+        for command in commands {
+            let registered = std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(String::as_str));
+            for name in registered {
+                if let Some(existing) = names.insert(name, &command.path) {
+                    panic!(
+                        "`{}` and `{}` are both exposed as `{}`, rename one of them",
+                        existing, command.path, name
+                    );
+                }
+            }
+            entries.insert(command.exposed_name(), (crate_name, command));
+        }
+    }
+
+    let object: Map<String, Value> = entries
+        .into_iter()
+        .map(|(name, (crate_name, command))| {
+            let mut fields = Map::new();
+            fields.insert("rust_path".into(), command.path.as_str().into());
+            fields.insert("crate".into(), crate_name.into());
+            if let Some(group) = &command.group {
+                fields.insert("group".into(), group.as_str().into());
+            }
+            if let Some(platform) = &command.platform {
+                fields.insert("platform".into(), platform.as_str().into());
+            }
+            if let Some(module) = &command.module {
+                fields.insert("module".into(), module.as_str().into());
+            }
+            if !command.aliases.is_empty() {
+                fields.insert("aliases".into(), command.aliases.clone().into());
+            }
+            if command.metadata_only {
+                fields.insert("metadata_only".into(), true.into());
+            }
+            if command.no_args {
+                fields.insert("no_args".into(), true.into());
+            }
+            if let Some(timeout_ms) = command.timeout_ms {
+                fields.insert("timeout_ms".into(), timeout_ms.into());
+            }
+            (name.to_string(), Value::Object(fields))
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&object).unwrap() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use serde_json::json;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn commands_are_keyed_by_their_exposed_name() {
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "settings", platform = "desktop")]
+                pub fn set_theme() {}
+                "#,
+            )
+            .file(
+                "users/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(rename = "get_user", alias = "fetch_user")]
+                pub fn load_user() {}
+                "#,
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let keyed: Value = serde_json::from_str(&generate_keyed_json(&scan)).unwrap();
+        assert_eq!(
+            keyed,
+            json!({
+                "get_user": {
+                    "rust_path": "users::load_user",
+                    "crate": "users",
+                    "aliases": ["fetch_user"]
+                },
+                "set_theme": {
+                    "rust_path": "settings::set_theme",
+                    "crate": "settings",
+                    "group": "settings",
+                    "platform": "desktop"
+                }
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`settings::greet` and `users::greet` are both exposed as `greet`")]
+    fn clashing_names_are_rejected() {
+        let command = "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n";
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file("settings/src/lib.rs", command)
+            .file("users/src/lib.rs", command);
+
+        generate_keyed_json(&fixture.scan(&TauriHelperOptions::default()));
+    }
+}
@@ -1,14 +1,60 @@
+use cache::ScanCache;
 use rayon::prelude::*;
+use scan::MemberScan;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::Path;
-use syn::parse_file;
-use tauri_helper_core::{find_workspace_dir, get_workspace, get_workspace_members};
-use walkdir::WalkDir;
-
-pub use tauri_helper_core::types::TauriHelperOptions;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri_helper_core::command::{
+    check_calling_crate, discover_command_files, discover_commands, is_command_path,
+    normalize_commands, read_command_file,
+};
+use tauri_helper_core::{
+    commands_dir_at, current_workspace_dir, get_commands_dir, get_member_manifest,
+    get_workspace_at, get_workspace_members,
+};
+
+mod c_header;
+mod cache;
+mod diff;
+mod dot;
+mod fast_scan;
+mod keyed;
+mod lockfile;
+mod manifest;
+mod module_index;
+mod modules;
+mod permissions;
+mod precompiled;
+mod reexports;
+mod registry;
+mod return_schema;
+mod review;
+mod scan;
+mod signature;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(test)]
+mod test_support;
+mod ts_bindings;
+mod validate;
+mod versions;
+#[cfg(feature = "watch")]
+mod watch;
+mod zod;
+
+pub use diff::{ManifestDiff, diff_manifests};
+pub use scan::WorkspaceScan;
+pub use tauri_helper_core::command::CommandInfo;
+pub use tauri_helper_core::types::{
+    DuplicatePolicy, NamingConvention, TauriHelperOptions, TauriHelperOptionsBuilder,
+};
 pub use tauri_helper_macros::*;
+pub use validate::{Discrepancy, validate_command_files, validate_command_files_with};
+#[cfg(feature = "watch")]
+pub use watch::watch_and_regenerate;
 
 #[allow(clippy::needless_doctest_main)]
 /// Scans the crate for functions annotated with `#[tauri::command]` and optionally `#[auto_collect_command]`,
@@ -22,7 +68,7 @@ pub use tauri_helper_macros::*;
 ///
 /// Add the following to your `build.rs` file:
 ///
-/// ```rust
+/// ```rust,ignore
 /// fn main() {
 ///     // Generate the command file for Tauri
 ///     tauri_helper::generate_command_file(tauri_helper::TauriHelperOptions::default());
@@ -37,10 +83,10 @@ pub use tauri_helper_macros::*;
 /// By default, this function looks for functions annotated with both `#[tauri::command]` and
 /// `#[auto_collect_command]`. For example:
 ///
-/// ```rust
+/// ```rust,ignore
 /// #[tauri::command]
 /// #[auto_collect_command]
-/// fn my_command() {
+/// pub fn my_command() {
 ///     println!("Some Command")
 /// }
 /// ```
@@ -51,11 +97,26 @@ pub use tauri_helper_macros::*;
 /// functions, regardless of whether they have the `#[auto_collect_command]` attribute. However,
 /// this behavior is not recommended unless explicitly needed.
 ///
+/// Private functions are skipped since the handler can't reach them, marked ones with a warning.
+/// Set `include_private` when the handler is generated in the module defining the commands.
+///
 /// # Output
 ///
 /// The generated file will be placed in the `tauri_commands_list` folder (relative to the crate root) inside of the target folder.
 /// The file will contain a list of all collected commands, which can be used by the Tauri application
 /// to register commands.
+/// Lines starting with `#` are comments, `command_file_header` starts each file with one naming
+/// its crate.
+///
+/// Set `TAURI_HELPER_COMMANDS_DIR` to a path relative to the workspace root to write the files
+/// somewhere else, e.g. a committed directory. The macros read the same variable.
+///
+/// The outputs never hold absolute paths, the files they mention are relative to the workspace
+/// root, so that they are identical wherever the workspace is checked out.
+///
+/// The number of commands of each member is also set as a `TAURI_HELPER_COMMAND_COUNT_<CRATE>`
+/// environment variable of the crate running the build script, the name of the command file being
+/// uppercased with `-` replaced by `_`, e.g. `env!("TAURI_HELPER_COMMAND_COUNT_SRC_TAURI")`.
 ///
 /// # Options
 ///
@@ -68,6 +129,288 @@ pub use tauri_helper_macros::*;
 ///   **Recommendation**: Keep this option set to `false` to ensure explicit control over which
 ///   commands are included in your Tauri application.
 ///
+/// # Denylist
+///
+/// Setting `denylist_file` points to a file listing command names, one per line, that must never
+/// be registered. The build fails if a scanned command is registered under one of them, which
+/// guards against exposing a dangerous command by accident.
+///
+/// # Consolidated manifest
+///
+/// The commands of every crate are also merged into `_all.json` in the commands directory, keyed
+/// by crate. Each run only replaces the crates it scanned, so members running their own build
+/// script (e.g. with `members` set to themselves) each update their section without clobbering
+/// the others. The file is locked while it is updated.
+///
+/// # Layout
+///
+/// The files of each member are found by following the `mod` declarations from its crate roots,
+/// which are read from the `[lib]` and `[[bin]]` targets of its manifest and default to
+/// `src/lib.rs` and `src/main.rs`. Module paths start from the actual crate root, e.g.
+/// `[lib] path = "rust/lib.rs"`, and `#[path]` attributes on modules are honored at any depth, so
+/// relocated sources are scanned as well. Remaining `.rs` files of `src` are scanned too. The
+/// module defining each command is recorded in the command files.
+///
+/// Each command is recorded under the shortest path other crates can reach it through, following
+/// `pub mod` declarations and `pub use` re-exports (globs included), e.g. `my_crate::greet` for a
+/// function defined in a private module and re-exported with `pub use internal::commands::*;`.
+/// A named re-export only counts along with the `__cmd__` macro Tauri generates next to the
+/// function, e.g. `pub use internal::{__cmd__greet, greet};`. Functions that can't be reached that way are assumed to be re-exported from the crate root.
+///
+/// Commands of another crate registered through a re-export in the app, e.g.
+/// `pub mod plugin_commands { pub use plugin::commands::*; }`, are mapped to it with
+/// `reexport_paths`: `plugin` to `my_app::plugin_commands` makes the handler refer to
+/// `crate::plugin_commands::greet` rather than `plugin::commands::greet`.
+///
+/// Files generated by a build script and included with
+/// `include!(concat!(env!("OUT_DIR"), "/commands.rs"))` are scanned from the `OUT_DIR` of the
+/// member including them, found in the build directory of the current profile. A member that
+/// wasn't built yet has no such file, its generated commands are skipped with a warning.
+///
+/// # Renaming
+///
+/// Commands are exposed under the name of their function unless renamed, either with
+/// `#[auto_collect_command(rename = "hello")]` or through `TauriHelperOptions::rename_map`.
+/// Renames are applied by `tauri_collect_commands!`.
+///
+/// # Ordering
+///
+/// With `stable_order` enabled, commands keep their position in the existing command files and
+/// new ones are appended, which keeps the diffs of committed command files small.
+///
+/// # Parameterless commands
+///
+/// With `tag_no_args` enabled, commands whose arguments are all injected by Tauri (`State`,
+/// `AppHandle`, ...) are tagged `no_args=true` in the command files and `"no_args": true` in the
+/// JSON outputs, the frontend invokes them with `{}`.
+///
+/// # Runtime registry
+///
+/// When run from a build script, a `tauri_commands.rs` file is also written into `OUT_DIR`. It
+/// defines a `CommandMeta` struct and a `COMMANDS` constant describing every collected command,
+/// which lets the application introspect its commands without any file IO:
+///
+/// ```rust,ignore
+/// mod commands {
+///     include!(concat!(env!("OUT_DIR"), "/tauri_commands.rs"));
+/// }
+///
+/// for command in commands::COMMANDS {
+///     println!("{} ({})", command.name, command.group);
+/// }
+/// ```
+///
+/// # Test stub
+///
+/// With `test_stub` enabled, a `tauri_command_stubs.rs` file is written into `OUT_DIR` too,
+/// defining a test that refers to every command without invoking it. It catches commands that no
+/// longer resolve after a refactor with a plain `cargo test`, without a Tauri runtime:
+///
+/// ```rust,ignore
+/// #[cfg(test)]
+/// mod command_stubs {
+///     include!(concat!(env!("OUT_DIR"), "/tauri_command_stubs.rs"));
+/// }
+/// ```
+///
+/// Generic commands can't be referred to without their type arguments, keep them out of the
+/// collected commands or leave the option disabled.
+///
+/// # Builder snippet
+///
+/// With `builder_snippet` enabled, a `tauri_builder.rs` file is written into `OUT_DIR` as well,
+/// defining a `tauri_builder()` function that wires `tauri_collect_commands!()` into a
+/// `tauri::Builder`:
+///
+/// ```rust,ignore
+/// include!(concat!(env!("OUT_DIR"), "/tauri_builder.rs"));
+///
+/// fn main() {
+///     tauri_builder()
+///         .run(tauri::generate_context!())
+///         .expect("error while running tauri application");
+/// }
+/// ```
+///
+/// # Statistics
+///
+/// With `verbose` enabled, a summary of the scan is printed as `cargo:warning` lines: the number
+/// of files scanned, the number of commands found per crate and in total, and the elapsed time.
+///
+/// # C header
+///
+/// Setting `c_header` writes a C header listing the command names, for applications embedding
+/// Tauri from C or C++:
+///
+/// ```c
+/// #define TAURI_COMMAND_GREET "greet"
+///
+/// static const char* TAURI_COMMANDS[] = {
+///     TAURI_COMMAND_GREET,
+///     NULL,
+/// };
+/// ```
+///
+/// # Review list
+///
+/// Setting `review_list` writes a sorted, reproducible list of the commands of each crate, meant
+/// to be committed so that changes to the exposed commands show up in code review:
+///
+/// ```text
+/// # Commands collected by tauri-helper, do not edit.
+///
+/// # crate: my_app
+/// my_app::greet as hello
+/// ```
+///
+/// # Fast scan
+///
+/// Setting `fast_scan` finds the commands by matching the text of the source files instead of
+/// parsing them with `syn`, for very large workspaces where the scan dominates the build. It trades
+/// accuracy for speed:
+///
+/// - only the functions directly following an attribute run holding the marker are collected, and
+///   only the `#[cfg(...)]` attributes of that run are recorded,
+/// - the arguments of the marker (`rename`, `platform`, ...) are ignored,
+/// - inline modules, `#[cfg(test)]` blocks, `#[auto_collect_commands(...)]`, `collect_all` and
+///   `OUT_DIR` includes aren't understood, and generic commands aren't detected,
+/// - only the crate roots and the files of `src` and of the directories of the crate roots are
+///   scanned, each one's module being guessed from its location, `#[path]` being ignored, and
+///   commands are referred to from the root of their crate, so they must be re-exported there,
+/// - signatures are unknown: the Zod schemas, `ts_bindings`, `return_schemas` and
+///   `signature_lock` aren't written, a warning naming them, and the files of an earlier scan are
+///   kept as they are, `tag_no_args` has no effect and the signature lock isn't checked.
+///
+/// # Scan cache
+///
+/// Setting `scan_cache` keeps the scan of every source file in a cache file, keyed by a hash of its
+/// content, so that only the files that changed are parsed again. Unlike modification times, the
+/// hashes survive fresh checkouts: caching the file in CI (e.g. under `target/`) skips parsing
+/// unchanged files there too. Changing the options affecting the scan invalidates the cache.
+///
+/// # Signature lock
+///
+/// Setting `signature_lock` writes a hash of the signature of each command, e.g. into a committed
+/// `tauri-commands.lock`. Changing the arguments or the return type of a command changes its hash,
+/// which shows up in review, and `validate_command_files_with` reports the commands whose
+/// signature differs from the lock.
+///
+/// # Graph
+///
+/// Setting `dot_graph` writes a Graphviz graph of the crates and the commands they define, grouped
+/// commands being drawn in a cluster per group. Render it with `dot -Tsvg commands.dot`.
+///
+/// # Keyed JSON
+///
+/// Setting `keyed_json` writes a JSON object describing every command keyed by the name it is
+/// exposed under, which the frontend can import to look commands up at runtime:
+///
+/// ```json
+/// { "greet": { "rust_path": "my_app::greet", "crate": "my_app", "group": "users" } }
+/// ```
+///
+/// # Module index
+///
+/// Setting `module_index` writes a JSON object listing the commands defined by each module, which
+/// follows the structure of the code rather than the groups:
+///
+/// ```json
+/// { "my_app": ["greet"], "my_app::settings": ["load_settings", "save_settings"] }
+/// ```
+///
+/// Two commands exposed under the same name make the build fail.
+///
+/// # Return schemas
+///
+/// Setting `return_schemas` writes a JSON Schema of the value each command resolves with, keyed by
+/// its exposed name, to generate typed responses on the frontend. `Result` is unwrapped to its `Ok`
+/// type and structs with named fields defined in the workspace are described by their fields,
+/// honoring `#[serde(rename)]`, `#[serde(skip)]` and `rename_all = "camelCase"`:
+///
+/// ```json
+/// { "load_user": { "type": "object", "properties": { "name": { "type": "string" } }, "required": ["name"] } }
+/// ```
+///
+/// Structs are looked up by name, types that can't be resolved, including generic parameters and
+/// names defined by several structs, are described by `{}`.
+///
+/// # TypeScript bindings
+///
+/// Setting `ts_bindings` writes a TypeScript module exporting a typed wrapper around `invoke` for
+/// every command, named after its exposed name in `camelCase`:
+///
+/// ```ts
+/// export const greet = (name: string): Promise<string> => invoke("greet", { name });
+/// ```
+///
+/// Like the Zod schemas, arguments injected by Tauri are skipped, `Result<T, E>` resolves with `T`
+/// and types that aren't primitives, collections, options or tuples are typed as `any`. The module
+/// imports `invoke` from `@tauri-apps/api/core`, the Tauri 2 location.
+///
+/// Setting `frontend_commands` writes the names the commands can be invoked with instead, aliases
+/// included, as a `COMMANDS` array and a `CommandName` union type:
+///
+/// ```ts
+/// export const COMMANDS = ["greet", "load_user"] as const;
+/// export type CommandName = (typeof COMMANDS)[number];
+/// ```
+///
+/// Both paths can point into the sources of the frontend, e.g. `../src/generated/commands.ts` for
+/// a `src-tauri` workspace, so that the frontend is up to date after every backend build. Missing
+/// directories are created.
+///
+/// # Permissions reference
+///
+/// Setting `permissions_reference` writes a JSON object mapping every name a command can be
+/// invoked with to the identifiers of the permissions Tauri 2 generates for it, to copy into the
+/// capability files:
+///
+/// ```json
+/// { "load_user": { "allow": "allow-load-user", "deny": "deny-load-user" } }
+/// ```
+///
+/// The permission files themselves are still generated by `tauri-build`; commands of a plugin are
+/// referenced with its prefix in the capabilities, e.g. `my-plugin:allow-load-user`.
+///
+/// # SQLite
+///
+/// With the `sqlite` feature enabled, setting `sqlite_db` writes a SQLite database with a
+/// `commands` table holding a row per command, to query the IPC surface from internal tooling:
+///
+/// ```sql
+/// SELECT name FROM commands WHERE crate = 'my_app' AND platform IS NULL;
+/// ```
+///
+/// The database is written anew on every build, `aliases` and `cfg` hold JSON arrays.
+///
+/// # Zod schemas
+///
+/// With the `zod` feature enabled, a `<crate>.zod.ts` file is written next to each command file,
+/// exporting a `z.object` schema for the arguments of every command (e.g. `greetArgs`) and a schema
+/// for the value it resolves with (e.g. `greetResult`), `Result<T, E>` being unwrapped to `T`.
+/// Arguments injected by Tauri such as `State` or `AppHandle` are skipped and unknown types map to
+/// `z.unknown()`.
+///
+/// # Precompiled commands
+///
+/// For reproducible release builds, the commands can be collected once by an earlier CI step and
+/// read back by setting `precompiled_commands`, skipping the scan. The artifact is the consolidated
+/// manifest of that build, `_all.json`, or the output of `cargo tauri-helper list --json` when the
+/// default options are used. The artifact only holds the command file entries: as with
+/// `fast_scan`, the outputs relying on signatures, the Zod schemas, `ts_bindings`,
+/// `return_schemas` and `signature_lock`, aren't written and the committed ones are kept.
+///
+/// Setting `check_precompiled` as well scans the workspace anyway and fails the build if the
+/// commands found differ from the artifact, e.g. in the job that checks the artifact is up to date.
+/// The outputs are then generated from the scan.
+///
+/// # Disabling the collection
+///
+/// Setting `TAURI_HELPER_DISABLE` skips the scan for quick local builds: the command files and the
+/// other outputs of the previous build are left untouched and the macros expand to the commands
+/// they list. Only the files of `OUT_DIR` and the command counts are written, from those command
+/// files. Unset it to collect the commands again, which reruns the build script.
+///
 /// # Notes
 ///
 /// - This function should only be called once per build, typically in the `build.rs` script.
@@ -75,15 +418,15 @@ pub use tauri_helper_macros::*;
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// #[tauri::command]
 /// #[auto_collect_command]
-/// fn greet(name: String) -> String {
+/// pub fn greet(name: String) -> String {
 ///     format!("Hello, {}!", name)
 /// }
 ///
 /// #[tauri::command]
-/// fn calculate_sum(a: i32, b: i32) -> i32 {
+/// pub fn calculate_sum(a: i32, b: i32) -> i32 {
 ///     a + b
 /// }
 /// ```
@@ -102,90 +445,1427 @@ pub use tauri_helper_macros::*;
 /// If the function encounters an error during file generation, it will log the error and exit the
 /// build process with a non-zero status code.
 pub fn generate_command_file(options: TauriHelperOptions) {
-    let workspace_root = find_workspace_dir(Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()));
-    let commands_dir = workspace_root.join("target").join("tauri_commands_list");
-    fs::create_dir_all(&commands_dir).unwrap();
+    write_command_files(&options);
+}
 
-    let workspace_members = options
-        .members
-        .clone()
-        .unwrap_or_else(|| get_workspace_members(&workspace_root));
+/// Same as [`generate_command_file`] but only scans the given members, skipping the discovery of
+/// the workspace members and the `members` option.
+///
+/// Relative paths are relative to the workspace root.
+///
+/// ```rust,ignore
+/// fn main() {
+///     tauri_helper::generate_command_file_for_members(
+///         tauri_helper::TauriHelperOptions::default(),
+///         &["src-tauri".into(), "crates/commands".into()],
+///     );
+/// }
+/// ```
+pub fn generate_command_file_for_members(options: TauriHelperOptions, members: &[PathBuf]) {
+    write_member_command_files_at(&current_workspace_dir(), &options, members);
+}
 
-    for member in &workspace_members {
+/// Same as [`generate_command_file_for_members`] for the workspace located in `workspace_root`.
+fn write_member_command_files_at(
+    workspace_root: &Path,
+    options: &TauriHelperOptions,
+    members: &[PathBuf],
+) {
+    if collection_disabled() {
+        skip_collection(workspace_root, options);
+        return;
+    }
+
+    let members: Vec<String> = members
+        .iter()
+        .map(|member| member.to_string_lossy().into_owned())
+        .collect();
+    let mut warnings = Vec::new();
+    if members.is_empty() {
+        warnings.push("no members were given, no crate is scanned".to_string());
+    }
+    write_scan(
+        scan_members_at(workspace_root, members, warnings, options),
+        options,
+    );
+}
+
+/// Adds a command to the command file of `crate_name`, for build scripts that generate commands
+/// rather than annotating them.
+///
+/// `path` is the full path of the function, e.g. `my_crate::generated::ping`, through which the
+/// crate collecting the commands reaches it. Appending a command already in the file does nothing.
+/// Call it after [`generate_command_file`], which rewrites the command files of the scanned crates:
+///
+/// ```rust,ignore
+/// fn main() {
+///     tauri_helper::generate_command_file(tauri_helper::TauriHelperOptions::default());
+///     tauri_helper::append_command("my_crate", "my_crate::generated::ping");
+/// }
+/// ```
+///
+/// Only the command file is updated, the other outputs describe the scanned commands.
+///
+/// # Panics
+///
+/// Panics if `path` isn't a valid Rust path with at least two segments, if `crate_name` isn't a
+/// plain file name or if the command file can't be written.
+pub fn append_command(crate_name: &str, path: &str) {
+    append_command_at(&get_commands_dir(), crate_name, path);
+}
+
+/// Same as [`append_command`] for the command files of `commands_dir`.
+fn append_command_at(commands_dir: &Path, crate_name: &str, path: &str) {
+    if !is_command_path(path) {
+        panic!(
+            "Invalid command path `{}`, expected a full path such as `my_crate::greet`",
+            path
+        );
+    }
+    if crate_name.is_empty() || crate_name.contains(['/', '\\', '.']) {
+        panic!("Invalid crate name `{}` for a command file", crate_name);
+    }
+
+    fs::create_dir_all(commands_dir).unwrap();
+    let command_file = commands_dir.join(format!("{}.txt", crate_name));
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(&command_file)
+        .unwrap_or_else(|e| panic!("Failed to open {}: {}", command_file.display(), e));
+    // other build scripts may append to the same file, the lock is released when it is closed
+    file.lock()
+        .unwrap_or_else(|e| panic!("Failed to lock {}: {}", command_file.display(), e));
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap();
+    if contents
+        .lines()
+        .filter_map(CommandInfo::from_line)
+        .any(|command| command.path == path)
+    {
+        return;
+    }
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        writeln!(file).unwrap();
+    }
+    writeln!(file, "{}", CommandInfo::new(path).to_line()).unwrap();
+}
+
+/// Scans the workspace, or reads the precompiled commands, and writes every output of
+/// [`generate_command_file`].
+fn write_command_files(options: &TauriHelperOptions) {
+    write_command_files_at(&current_workspace_dir(), options);
+}
+
+/// Same as [`write_command_files`] for the workspace located in `workspace_root`.
+fn write_command_files_at(workspace_root: &Path, options: &TauriHelperOptions) {
+    if collection_disabled() {
+        skip_collection(workspace_root, options);
+        return;
+    }
+
+    let Some(precompiled) = &options.precompiled_commands else {
+        write_scan(scan_workspace_at(workspace_root, options), options);
+        return;
+    };
+
+    let artifact = workspace_root.join(precompiled);
+    println!("cargo:rerun-if-changed={}", artifact.display());
+    let scan = if options.check_precompiled {
+        let scan = scan_workspace_at(workspace_root, options);
+        precompiled::check_precompiled(&artifact, &scan);
+        scan
+    } else {
+        precompiled::read_precompiled(workspace_root, &artifact)
+    };
+    write_scan(scan, options);
+}
+
+/// Writes every output of [`generate_command_file`] for a scan.
+fn write_scan(scan: WorkspaceScan, options: &TauriHelperOptions) {
+    let commands_dir = commands_dir_at(&scan.workspace_root);
+    fs::create_dir_all(&commands_dir)
+        .unwrap_or_else(|e| panic!("Failed to create {}: {}", commands_dir.display(), e));
+    println!("cargo:rerun-if-env-changed=TAURI_HELPER_COMMANDS_DIR");
+
+    for member in scan.members.iter().flat_map(|scan| &scan.members) {
         println!("cargo:rerun-if-changed={}", member);
     }
+    let lockfile = scan.workspace_root.join("Cargo.lock");
+    if lockfile.exists() {
+        println!("cargo:rerun-if-changed={}", lockfile.display());
+    }
 
-    workspace_members.par_iter().for_each(|member| {
-        let manifest_dir = workspace_root.join(member);
-        let crate_name = manifest_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or_default()
-            .to_string();
+    if options.deny_warnings {
+        let warnings: Vec<&str> = scan.warnings().collect();
+        if !warnings.is_empty() {
+            panic!(
+                "`deny_warnings` is set and the scan raised {} warning(s):\n  {}",
+                warnings.len(),
+                warnings.join("\n  ")
+            );
+        }
+    }
+    scan::emit_warnings(&scan);
+    if options.verbose {
+        scan::report_summary(&scan.members, scan.elapsed, scan.files_cached);
+    }
 
-        let src_dir = manifest_dir.join("src");
-        let mut functions = Vec::new();
+    let unannotated: Vec<&str> = scan
+        .members
+        .iter()
+        .flat_map(|member| member.unannotated.iter().map(String::as_str))
+        .collect();
+    if !unannotated.is_empty() {
+        panic!(
+            "`require_annotation` is set but these `#[tauri::command]` functions lack `#[{}]`:\n  {}",
+            options.marker_attribute,
+            unannotated.join("\n  ")
+        );
+    }
 
-        let rs_files: Vec<_> = WalkDir::new(&src_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("rs"))
-            .collect();
+    if let Some(denylist_file) = &options.denylist_file {
+        let denylist_file = scan.workspace_root.join(denylist_file);
+        println!("cargo:rerun-if-changed={}", denylist_file.display());
+        check_denylist(&denylist_file, &scan);
+    }
 
-        let file_fns: Vec<String> = rs_files
-            .par_iter()
-            .filter_map(|entry| {
-                let path = entry.path();
-                let content = fs::read_to_string(path).ok()?;
-                if !content.contains("tauri::command") && !content.contains("auto_collect_command")
-                {
-                    return None;
-                }
+    for member in &scan.members {
+        if member.commands.is_empty() {
+            continue;
+        }
+
+        let command_file = commands_dir.join(format!("{}.txt", member.crate_name));
+        let mut commands: Vec<&CommandInfo> = member.commands.iter().collect();
+        if options.stable_order
+            && let Some(previous) = read_command_file(&command_file)
+        {
+            keep_previous_order(&mut commands, &previous);
+        }
+
+        let mut contents = String::new();
+        if options.command_file_header {
+            contents.push_str(&format!(
+                "# generated by tauri-helper for crate {}\n",
+                member.crate_name
+            ));
+        }
+        for command in commands {
+            contents.push_str(&command.to_line());
+            contents.push('\n');
+        }
+        fs::write(&command_file, contents)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", command_file.display(), e));
+
+        if cfg!(feature = "zod") && scan.signatures_known {
+            let zod_file = format!("{}.zod.ts", member.crate_name);
+            write_output(&commands_dir, Some(Path::new(&zod_file)), || {
+                zod::generate_zod_schemas(member)
+            });
+        }
+    }
+
+    for member in &scan.members {
+        emit_command_count(&member.crate_name, member.commands.len());
+    }
+
+    manifest::merge_manifest(&commands_dir.join("_all.json"), &scan);
+
+    // the outputs describing the signatures would be written empty, the ones of an earlier scan,
+    // e.g. a committed signature lock, are left as they are
+    if !scan.signatures_known {
+        warn_signature_outputs(options);
+    }
+    let signature_output = |path| if scan.signatures_known { path } else { None };
+
+    let root = &scan.workspace_root;
+    write_output(root, options.review_list.as_deref(), || {
+        review::generate_review_list(&scan)
+    });
+    write_output(
+        root,
+        signature_output(options.signature_lock.as_deref()),
+        || lockfile::generate_lockfile(&scan),
+    );
+    write_output(root, options.dot_graph.as_deref(), || {
+        dot::generate_dot(&scan)
+    });
+    write_output(root, options.keyed_json.as_deref(), || {
+        keyed::generate_keyed_json(&scan)
+    });
+    write_output(root, options.module_index.as_deref(), || {
+        module_index::generate_module_index(&scan)
+    });
+    if let Some(sqlite_db) = &options.sqlite_db {
+        write_sqlite(&root.join(sqlite_db), &scan);
+    }
+    write_output(
+        root,
+        signature_output(options.return_schemas.as_deref()),
+        || return_schema::generate_return_schemas(&scan),
+    );
+    write_output(
+        root,
+        signature_output(options.ts_bindings.as_deref()),
+        || ts_bindings::generate_ts_bindings(&scan),
+    );
+    write_output(root, options.frontend_commands.as_deref(), || {
+        ts_bindings::generate_command_names(&scan)
+    });
+    write_output(root, options.permissions_reference.as_deref(), || {
+        permissions::generate_permissions_reference(&scan)
+    });
+
+    let collected: Vec<CommandInfo> = scan.commands().cloned().collect();
+    write_output(root, options.c_header.as_deref(), || {
+        c_header::generate_c_header(&collected)
+    });
+
+    write_out_dir(&scan.workspace_root, collected, options);
+}
+
+/// Warns that the outputs relying on the signatures of the commands aren't written, when the
+/// signatures are unknown.
+fn warn_signature_outputs(options: &TauriHelperOptions) {
+    let mut skipped: Vec<&str> = [
+        ("`signature_lock`", &options.signature_lock),
+        ("`ts_bindings`", &options.ts_bindings),
+        ("`return_schemas`", &options.return_schemas),
+    ]
+    .into_iter()
+    .filter(|(_, output)| output.is_some())
+    .map(|(name, _)| name)
+    .collect();
+    if cfg!(feature = "zod") {
+        skipped.push("the Zod schemas");
+    }
+    if !skipped.is_empty() {
+        println!(
+            "cargo:warning=tauri-helper: the signatures of the commands are unknown, {} are not written and the files of an earlier scan are kept",
+            skipped.join(", ")
+        );
+    }
+}
+
+/// Writes the output generated by `generate` to `path`, relative to `root`, if the option setting
+/// it is set. Missing directories are created.
+///
+/// # Panics
+///
+/// Panics naming the file if it can't be written.
+fn write_output(root: &Path, path: Option<&Path>, generate: impl FnOnce() -> String) {
+    let Some(path) = path else {
+        return;
+    };
+    let file = root.join(path);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("Failed to create {}: {}", parent.display(), e));
+    }
+    fs::write(&file, generate())
+        .unwrap_or_else(|e| panic!("Failed to write {}: {}", file.display(), e));
+}
+
+/// Writes the files included from `OUT_DIR`, when the build script is run by Cargo.
+fn write_out_dir(workspace_root: &Path, collected: Vec<CommandInfo>, options: &TauriHelperOptions) {
+    let Ok(out_dir) = env::var("OUT_DIR") else {
+        return;
+    };
 
-                let ast = parse_file(&content).ok()?;
-                let mut found = Vec::new();
-
-                for item in ast.items {
-                    if let syn::Item::Fn(func) = item {
-                        if !options.collect_all {
-                            if func
-                                .attrs
-                                .iter()
-                                .any(|a| a.path().is_ident("auto_collect_command"))
-                            {
-                                found.push(func.sig.ident.to_string());
-                            }
-                        } else if func.attrs.iter().any(|a| {
-                            let p = a.path();
-                            p.is_ident("command")
-                                || (p.segments.len() == 2
-                                    && p.segments[0].ident == "tauri"
-                                    && p.segments[1].ident == "command")
-                        }) {
-                            found.push(func.sig.ident.to_string());
-                        }
-                    }
+    let out_dir = Path::new(&out_dir);
+    let [registry_file, builder_file, stubs_file] = registry::OUT_DIR_FILES.map(Path::new);
+    write_output(out_dir, Some(registry_file), || {
+        registry::generate_registry(&collected)
+    });
+    if options.builder_snippet {
+        write_output(out_dir, Some(builder_file), || {
+            registry::BUILDER_SNIPPET.to_string()
+        });
+    }
+    if options.test_stub {
+        // the commands of the crate being built are referred to through `crate::`
+        let workspace_package = get_workspace_at(workspace_root).package.name;
+        let calling_crate = tauri_helper_core::macro_support::calling_crate();
+        if let Err(e) = check_calling_crate(&workspace_package, &calling_crate, &collected) {
+            println!("cargo:warning=tauri-helper: {}", e);
+        }
+        let commands = normalize_commands(&workspace_package, &calling_crate, collected);
+        write_output(out_dir, Some(stubs_file), || {
+            registry::generate_test_stub(commands.iter())
+        });
+    }
+}
+
+/// Sets the `TAURI_HELPER_COMMAND_COUNT_<CRATE>` variable of a command file.
+fn emit_command_count(crate_name: &str, count: usize) {
+    let key: String = crate_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    println!(
+        "cargo:rustc-env=TAURI_HELPER_COMMAND_COUNT_{}={}",
+        key, count
+    );
+}
+
+/// Returns `true` if `TAURI_HELPER_DISABLE` is set, the collection being skipped.
+fn collection_disabled() -> bool {
+    println!("cargo:rerun-if-env-changed=TAURI_HELPER_DISABLE");
+    env::var_os("TAURI_HELPER_DISABLE").is_some()
+}
+
+/// Skips the collection, keeping the command files of the previous build as they are.
+///
+/// The files of `OUT_DIR` and the command counts still come from the existing command files, so
+/// that the crates relying on them keep compiling.
+fn skip_collection(workspace_root: &Path, options: &TauriHelperOptions) {
+    println!(
+        "cargo:warning=tauri-helper: `TAURI_HELPER_DISABLE` is set, the commands are not collected"
+    );
+    let files = discover_command_files(&commands_dir_at(workspace_root)).unwrap_or_default();
+    for file in &files {
+        if let Some(crate_name) = file.path.file_stem() {
+            emit_command_count(&crate_name.to_string_lossy(), file.commands.len());
+        }
+    }
+    let commands = files.into_iter().flat_map(|file| file.commands).collect();
+    write_out_dir(workspace_root, commands, options);
+}
+
+#[cfg(feature = "sqlite")]
+fn write_sqlite(db_file: &Path, scan: &WorkspaceScan) {
+    if let Some(parent) = db_file.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("Failed to create {}: {}", parent.display(), e));
+    }
+    // the database is written anew, rows of removed commands don't linger
+    if db_file.exists() {
+        fs::remove_file(db_file)
+            .unwrap_or_else(|e| panic!("Failed to remove {}: {}", db_file.display(), e));
+    }
+    sqlite::write_sqlite(db_file, scan)
+        .unwrap_or_else(|e| panic!("Failed to write {}: {}", db_file.display(), e));
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn write_sqlite(_db_file: &Path, _scan: &WorkspaceScan) {
+    println!("cargo:warning=tauri-helper: `sqlite_db` is set but the `sqlite` feature is disabled");
+}
+
+/// Fails the build if a command that would be registered is listed in the denylist file.
+fn check_denylist(denylist_file: &Path, scan: &WorkspaceScan) {
+    let contents = fs::read_to_string(denylist_file).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read the denylist file {}: {}",
+            denylist_file.display(),
+            e
+        )
+    });
+    let denied: HashSet<&str> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    let offenders: Vec<String> = scan
+        .commands()
+        // metadata-only commands are never registered
+        .filter(|command| !command.metadata_only)
+        .filter(|command| {
+            denied.contains(command.path.as_str())
+                || denied.contains(command.exposed_name())
+                || command
+                    .aliases
+                    .iter()
+                    .any(|alias| denied.contains(alias.as_str()))
+        })
+        .map(|command| format!("`{}` ({})", command.exposed_name(), command.path))
+        .collect();
+
+    if !offenders.is_empty() {
+        panic!(
+            "These commands are listed in the denylist file {} and must not be registered:\n  {}",
+            denylist_file.display(),
+            offenders.join("\n  ")
+        );
+    }
+}
+
+/// Orders the commands that were already in the previous command file as they were, followed by
+/// the new ones in the order they were found.
+fn keep_previous_order(commands: &mut [&CommandInfo], previous: &[CommandInfo]) {
+    let positions: HashMap<&str, usize> = previous
+        .iter()
+        .enumerate()
+        .map(|(i, command)| (command.path.as_str(), i))
+        .collect();
+    // the sort is stable, new commands keep their relative order
+    commands.sort_by_key(|command| {
+        positions
+            .get(command.path.as_str())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+}
+
+/// Scans the workspace for commands without writing anything.
+///
+/// This is the first half of [`generate_command_file`], the returned [`WorkspaceScan`] holds the
+/// commands of each member exactly as they would be written to the command files.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`generate_command_file`], e.g. when the workspace root can't be
+/// found or an `#[auto_collect_command]` attribute is invalid.
+pub fn scan_workspace(options: &TauriHelperOptions) -> WorkspaceScan {
+    let workspace_root = current_workspace_dir();
+    scan_workspace_at(&workspace_root, options)
+}
+
+/// Same as [`scan_workspace`] for the workspace located in `workspace_root`, for tools running
+/// outside of a build script.
+pub fn scan_workspace_at(workspace_root: &Path, options: &TauriHelperOptions) -> WorkspaceScan {
+    let mut warnings = Vec::new();
+    let workspace_members = match (&options.members, options.scan_packages.is_empty()) {
+        (Some(members), true) => members.clone(),
+        (None, true) => get_workspace_members(workspace_root),
+        (members, false) => {
+            let mut members = members.clone().unwrap_or_default();
+            for member in resolve_packages(workspace_root, &options.scan_packages, &mut warnings) {
+                if !members.contains(&member) {
+                    members.push(member);
                 }
-                Some(found)
-            })
-            .flatten()
-            .collect();
+            }
+            members
+        }
+    };
+
+    if workspace_members.is_empty() && !options.scan_packages.is_empty() {
+        warnings.push(
+            "none of the `scan_packages` is a workspace member, no crate is scanned".to_string(),
+        );
+    } else if workspace_members.is_empty() && options.members.is_some() {
+        warnings.push(
+            "the `members` option is empty, no crate is scanned. Use `None` to scan every workspace member".to_string(),
+        );
+    } else if workspace_members.is_empty() {
+        warnings.push(format!(
+            "no workspace members to scan, check the `members` of the `[workspace]` section in {} (it should at least contain \".\")",
+            workspace_root.join("Cargo.toml").display()
+        ));
+    }
 
-        functions.extend(file_fns);
+    scan_members_at(workspace_root, workspace_members, warnings, options)
+}
 
-        if !functions.is_empty() {
-            let package_name = get_workspace().package.name.replace('-', "_");
-            let command_file = commands_dir.join(format!("{}.txt", crate_name));
-            let mut file = File::create(&command_file).unwrap();
+/// Returns the members defining the given packages, warning about the packages no member defines.
+fn resolve_packages(
+    workspace_root: &Path,
+    packages: &[String],
+    warnings: &mut Vec<String>,
+) -> Vec<String> {
+    let members: Vec<(String, String)> = get_workspace_members(workspace_root)
+        .into_iter()
+        .filter_map(|member| {
+            let package = get_member_manifest(&workspace_root.join(&member)).package?;
+            Some((package.name.replace('-', "_"), member))
+        })
+        .collect();
 
-            for func in functions {
-                let full_name = if crate_name.replace('-', "_") == "src_tauri" {
-                    format!("{}::{}", package_name, func)
-                } else {
-                    format!("{}::{}", crate_name.replace('-', "_"), func)
-                };
-                writeln!(file, "{}", full_name).unwrap();
+    packages
+        .iter()
+        .filter_map(|package| {
+            let member = members
+                .iter()
+                .find(|(name, _)| *name == package.replace('-', "_"))
+                .map(|(_, member)| member.clone());
+            if member.is_none() {
+                warnings.push(format!(
+                    "package `{}` of `scan_packages` is not defined by any workspace member, it is not scanned",
+                    package
+                ));
             }
+            member
+        })
+        .collect()
+}
+
+/// Scans the given members of the workspace located in `workspace_root`, `warnings` being the
+/// ones raised while resolving the members.
+fn scan_members_at(
+    workspace_root: &Path,
+    workspace_members: Vec<String>,
+    mut warnings: Vec<String>,
+    options: &TauriHelperOptions,
+) -> WorkspaceScan {
+    // only the resolved versions tell whether the specta crates match tauri
+    if let Ok(lockfile) = fs::read_to_string(workspace_root.join("Cargo.lock")) {
+        warnings.extend(versions::version_warnings(&lockfile));
+    }
+
+    let started = Instant::now();
+    let package_name = get_workspace_at(workspace_root)
+        .package
+        .name
+        .replace('-', "_");
+
+    // directories outside of the workspace are scanned like members
+    let extra_paths = options
+        .extra_scan_paths
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned());
+    let members_to_scan: Vec<String> = workspace_members.into_iter().chain(extra_paths).collect();
+
+    let cache = options
+        .scan_cache
+        .as_ref()
+        .map(|cache_file| ScanCache::load(workspace_root, &workspace_root.join(cache_file)));
+    let scan = || -> Vec<MemberScan> {
+        members_to_scan
+            .par_iter()
+            .map(|member| {
+                scan::scan_member(
+                    workspace_root,
+                    member,
+                    &package_name,
+                    options,
+                    cache.as_ref(),
+                )
+            })
+            .collect()
+    };
+    // the files of the members are scanned by the threads of the pool the members are scanned in
+    let scans = match options.scan_threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .build()
+            .unwrap_or_else(|e| panic!("Failed to start the scanning threads: {}", e))
+            .install(scan),
+        None => scan(),
+    };
+    let files_cached = cache.as_ref().map_or(0, ScanCache::hits);
+    if let (Some(cache), Some(cache_file)) = (cache, &options.scan_cache) {
+        cache.save(&workspace_root.join(cache_file));
+    }
+    let (mut members, merge_warnings) = scan::group_by_crate(scans);
+    warnings.extend(merge_warnings);
+    warnings.extend(scan::apply_duplicate_policy(
+        &mut members,
+        options.on_duplicate,
+    ));
+    if let Some(convention) = options.name_convention {
+        warnings.extend(scan::naming_warnings(&members, convention));
+    }
+
+    WorkspaceScan {
+        workspace_root: workspace_root.to_path_buf(),
+        members,
+        warnings,
+        elapsed: started.elapsed(),
+        files_cached,
+        signatures_known: !options.fast_scan,
+    }
+}
+
+/// Returns every command group and the names of the commands it contains.
+///
+/// Groups are set with `#[auto_collect_command(group = "...")]` and read back from the command files
+/// written by [`generate_command_file`], commands without a group are not part of the map.
+///
+/// ```rust,ignore
+/// #[tauri::command]
+/// #[auto_collect_command(group = "settings")]
+/// pub fn set_theme(theme: String) {}
+///
+/// let groups = tauri_helper::command_groups();
+/// assert_eq!(groups["settings"], vec!["set_theme".to_string()]);
+/// ```
+pub fn command_groups() -> BTreeMap<String, Vec<String>> {
+    command_groups_in(&get_commands_dir())
+}
+
+/// Same as [`command_groups`] for the command files of `commands_dir`.
+fn command_groups_in(commands_dir: &Path) -> BTreeMap<String, Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for command in discover_commands(commands_dir).unwrap_or_default() {
+        if let Some(group) = &command.group {
+            groups
+                .entry(group.clone())
+                .or_default()
+                .push(command.fn_name().to_string());
         }
+    }
+
+    for commands in groups.values_mut() {
+        commands.sort();
+    }
+
+    groups
+}
+
+/// Asserts that the names the collected commands can be invoked with, aliases included, are
+/// exactly `expected`, in any order. Meant for a snapshot test of the IPC surface:
+///
+/// ```rust,ignore
+/// #[test]
+/// fn commands_snapshot() {
+///     tauri_helper::assert_commands_snapshot(&["greet", "load_user"]);
+/// }
+/// ```
+///
+/// The commands are read from the command files, `metadata_only` ones excluded.
+///
+/// # Panics
+///
+/// Panics if the command files can't be found, or listing the added and removed names on a
+/// mismatch:
+///
+/// ```text
+/// The collected commands don't match the snapshot:
+///   + save_user (not in the snapshot)
+///   - load_user (no longer collected)
+/// ```
+pub fn assert_commands_snapshot(expected: &[&str]) {
+    assert_commands_snapshot_in(&get_commands_dir(), expected);
+}
+
+/// Same as [`assert_commands_snapshot`] for the command files of `commands_dir`.
+fn assert_commands_snapshot_in(commands_dir: &Path, expected: &[&str]) {
+    let commands = discover_commands(commands_dir).unwrap_or_else(|| {
+        panic!(
+            "No command files found in {}, run the build script first",
+            commands_dir.display()
+        )
     });
+
+    let found: BTreeSet<&str> = commands
+        .iter()
+        .filter(|command| !command.metadata_only)
+        .flat_map(|command| {
+            std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(String::as_str))
+        })
+        .collect();
+    let expected: BTreeSet<&str> = expected.iter().copied().collect();
+
+    let mut mismatches: Vec<String> = found
+        .difference(&expected)
+        .map(|name| format!("+ {} (not in the snapshot)", name))
+        .collect();
+    mismatches.extend(
+        expected
+            .difference(&found)
+            .map(|name| format!("- {} (no longer collected)", name)),
+    );
+
+    if !mismatches.is_empty() {
+        panic!(
+            "The collected commands don't match the snapshot:\n  {}",
+            mismatches.join("\n  ")
+        );
+    }
+}
+
+/// Returns the workspace members, as listed in the workspace manifest, in which no command was
+/// found, which often points to a missing `#[auto_collect_command]`.
+///
+/// ```rust,ignore
+/// let empty = tauri_helper::members_without_commands(&tauri_helper::TauriHelperOptions::default());
+/// assert!(empty.is_empty(), "members without commands: {:?}", empty);
+/// ```
+///
+/// # Panics
+///
+/// Panics in the same cases as [`scan_workspace`].
+pub fn members_without_commands(options: &TauriHelperOptions) -> Vec<String> {
+    members_without_commands_at(&current_workspace_dir(), options)
+}
+
+/// Same as [`members_without_commands`] for the workspace located in `workspace_root`.
+fn members_without_commands_at(workspace_root: &Path, options: &TauriHelperOptions) -> Vec<String> {
+    scan_workspace_at(workspace_root, options)
+        .members
+        .into_iter()
+        .filter(|member| member.commands.is_empty())
+        .flat_map(|member| member.members)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::Fixture;
+
+    /// Writes the command files of a crate defining `greet` and `load_user`, also invoked as
+    /// `get_user`.
+    fn collected() -> Fixture {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(alias = "get_user")]
+            pub fn load_user() {}
+            "#,
+        );
+        write_command_files_at(fixture.root(), &TauriHelperOptions::default());
+        fixture
+    }
+
+    #[test]
+    fn commands_are_listed_under_their_group() {
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "settings")]
+                pub fn set_theme() {}
+
+                #[tauri::command]
+                #[auto_collect_command(group = "settings")]
+                pub fn get_theme() {}
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn ungrouped() {}
+                "#,
+            )
+            .file(
+                "users/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "users")]
+                pub fn load_user() {}
+                "#,
+            );
+        write_command_files_at(fixture.root(), &TauriHelperOptions::default());
+
+        let groups = command_groups_in(&commands_dir_at(fixture.root()));
+        assert_eq!(
+            groups,
+            BTreeMap::from([
+                (
+                    "settings".to_string(),
+                    vec!["get_theme".to_string(), "set_theme".to_string()]
+                ),
+                ("users".to_string(), vec!["load_user".to_string()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn snapshot_matches_in_any_order() {
+        let fixture = collected();
+        assert_commands_snapshot_in(
+            &commands_dir_at(fixture.root()),
+            &["load_user", "get_user", "greet"],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The collected commands don't match the snapshot:\n  \
+        + get_user (not in the snapshot)\n  \
+        - save_user (no longer collected)")]
+    fn snapshot_mismatch_lists_added_and_removed_names() {
+        let fixture = collected();
+        assert_commands_snapshot_in(
+            &commands_dir_at(fixture.root()),
+            &["greet", "load_user", "save_user"],
+        );
+    }
+
+    #[test]
+    fn empty_members_are_reported() {
+        let fixture = Fixture::workspace(&[]);
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert!(scan.commands().next().is_none());
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "no workspace members to scan, check the `members` of the `[workspace]` section in {} (it should at least contain \".\")",
+                fixture.root().join("Cargo.toml").display()
+            )]
+        );
+
+        let options = TauriHelperOptions::builder()
+            .members(Vec::<String>::new())
+            .build();
+        assert_eq!(
+            fixture.scan(&options).warnings().collect::<Vec<_>>(),
+            [
+                "the `members` option is empty, no crate is scanned. Use `None` to scan every workspace member"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`require_annotation` is set but these `#[tauri::command]` functions lack `#[auto_collect_command]`:\n  `unmarked` at "
+    )]
+    fn unannotated_commands_fail_with_require_annotation() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            pub fn unmarked() {}
+            "#,
+        );
+        let options = TauriHelperOptions::builder()
+            .require_annotation(true)
+            .build();
+
+        write_command_files_at(fixture.root(), &options);
+    }
+
+    #[test]
+    fn unannotated_commands_are_located() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n\n#[tauri::command]\npub fn unmarked() {}\n",
+        );
+        let options = TauriHelperOptions::builder()
+            .require_annotation(true)
+            .build();
+
+        assert_eq!(
+            fixture.scan(&options).members[0].unannotated,
+            [format!(
+                "`unmarked` at {}:6",
+                fixture.root().join("cmds/src/lib.rs").display()
+            )]
+        );
+        assert!(
+            fixture.scan(&TauriHelperOptions::default()).members[0]
+                .unannotated
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn stable_order_keeps_the_previous_order() {
+        let command = |name: &str| {
+            format!(
+                "#[tauri::command]\n#[auto_collect_command]\npub fn {}() {{}}\n",
+                name
+            )
+        };
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            &[command("save_user"), command("greet")].concat(),
+        );
+        let options = TauriHelperOptions::builder().stable_order(true).build();
+        write_command_files_at(fixture.root(), &options);
+
+        // `load_user` is added between the existing commands, which are swapped
+        let fixture = fixture.file(
+            "cmds/src/lib.rs",
+            &[command("greet"), command("load_user"), command("save_user")].concat(),
+        );
+        write_command_files_at(fixture.root(), &options);
+
+        let paths: Vec<String> =
+            read_command_file(&commands_dir_at(fixture.root()).join("cmds.txt"))
+                .unwrap()
+                .into_iter()
+                .map(|command| command.path)
+                .collect();
+        assert_eq!(paths, ["cmds::save_user", "cmds::greet", "cmds::load_user"]);
+    }
+
+    #[test]
+    fn members_without_annotated_functions_are_listed() {
+        let fixture = Fixture::workspace(&["helpers", "users"])
+            .file(
+                "helpers/src/lib.rs",
+                "#[tauri::command]\npub fn forgotten() {}\n\npub fn helper() {}\n",
+            )
+            .file(
+                "users/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            );
+
+        assert_eq!(
+            members_without_commands_at(fixture.root(), &TauriHelperOptions::default()),
+            ["helpers"]
+        );
+    }
+
+    #[test]
+    fn extra_scan_paths_outside_of_the_workspace_are_scanned() {
+        // the workspace is `app`, next to the shared crate
+        let fixture = Fixture::workspace(&[])
+            .file(
+                "app/Cargo.toml",
+                "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nshared = { path = \"../shared\" }\n\n[workspace]\nmembers = [\".\"]\n",
+            )
+            .file(
+                "app/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "shared/Cargo.toml",
+                "[package]\nname = \"shared\"\nversion = \"0.1.0\"\n",
+            )
+            .file(
+                "shared/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn ping() {}\n",
+            );
+        let workspace_root = fixture.root().join("app");
+
+        let paths = |options: &TauriHelperOptions| -> Vec<String> {
+            scan_workspace_at(&workspace_root, options)
+                .commands()
+                .map(|command| command.path.clone())
+                .collect()
+        };
+        assert_eq!(paths(&TauriHelperOptions::default()), ["app::greet"]);
+        let options = TauriHelperOptions::builder()
+            .extra_scan_path("../shared")
+            .build();
+        assert_eq!(paths(&options), ["app::greet", "shared::ping"]);
+    }
+
+    /// A crate defining `greet` next to a module that can't be parsed.
+    fn unparsable_module() -> Fixture {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "pub mod broken;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/broken.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn broken( {}\n",
+            )
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`deny_warnings` is set and the scan raised 1 warning(s):\n  could not parse "
+    )]
+    fn warnings_fail_the_build_with_deny_warnings() {
+        let fixture = unparsable_module();
+        let options = TauriHelperOptions::builder().deny_warnings(true).build();
+
+        write_command_files_at(fixture.root(), &options);
+    }
+
+    #[test]
+    fn warnings_dont_fail_the_build_by_default() {
+        let fixture = unparsable_module();
+
+        write_command_files_at(fixture.root(), &TauriHelperOptions::default());
+        let commands = read_command_file(&commands_dir_at(fixture.root()).join("cmds.txt"));
+        assert_eq!(commands.unwrap()[0].path, "cmds::greet");
+    }
+
+    /// A crate defining `greet` and `delete_everything`, also invoked as `wipe`.
+    fn dangerous_commands(denylist: &str) -> Fixture {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn greet() {}
+
+                #[tauri::command]
+                #[auto_collect_command(alias = "wipe")]
+                pub fn delete_everything() {}
+                "#,
+            )
+            .file("denylist.txt", denylist)
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "denylist.txt and must not be registered:\n  `delete_everything` (cmds::delete_everything)"
+    )]
+    fn denied_commands_fail_the_build() {
+        let fixture = dangerous_commands("# never expose these\nwipe\n");
+        let options = TauriHelperOptions::builder()
+            .denylist_file("denylist.txt")
+            .build();
+
+        write_command_files_at(fixture.root(), &options);
+    }
+
+    #[test]
+    fn commands_absent_from_the_denylist_are_written() {
+        let fixture = dangerous_commands("shutdown\n");
+        let options = TauriHelperOptions::builder()
+            .denylist_file("denylist.txt")
+            .build();
+
+        write_command_files_at(fixture.root(), &options);
+        assert!(commands_dir_at(fixture.root()).join("cmds.txt").exists());
+    }
+
+    #[test]
+    fn only_the_given_members_are_scanned() {
+        let command = "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n";
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file("settings/src/lib.rs", command)
+            .file("users/src/lib.rs", command)
+            // not a workspace member
+            .file(
+                "tools/cli/Cargo.toml",
+                "[package]\nname = \"cli\"\nversion = \"0.1.0\"\n",
+            )
+            .file("tools/cli/src/lib.rs", command);
+
+        write_member_command_files_at(
+            fixture.root(),
+            &TauriHelperOptions::default(),
+            &["users".into(), "tools/cli".into()],
+        );
+        let mut written: Vec<String> = fs::read_dir(commands_dir_at(fixture.root()))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".txt"))
+            .collect();
+        written.sort();
+        assert_eq!(written, ["cli.txt", "users.txt"]);
+    }
+
+    #[test]
+    fn parallel_scans_attribute_commands_to_their_crate() {
+        let crates = ["settings", "users", "windows"];
+        let mut fixture = Fixture::workspace(&crates);
+        for crate_name in crates {
+            let modules: Vec<String> = (0..10).map(|i| format!("pub mod m{};\n", i)).collect();
+            fixture = fixture.file(&format!("{}/src/lib.rs", crate_name), &modules.concat());
+            for i in 0..10 {
+                fixture = fixture.file(
+                    &format!("{}/src/m{}.rs", crate_name, i),
+                    &format!(
+                        "#[tauri::command]\n#[auto_collect_command]\npub fn {}_{}() {{}}\n",
+                        crate_name, i
+                    ),
+                );
+            }
+        }
+
+        let command_files = |threads: usize| -> Vec<(String, Vec<String>)> {
+            write_command_files_at(
+                fixture.root(),
+                &TauriHelperOptions::builder().scan_threads(threads).build(),
+            );
+            crates
+                .iter()
+                .map(|crate_name| {
+                    let file = commands_dir_at(fixture.root()).join(format!("{}.txt", crate_name));
+                    let paths = read_command_file(&file)
+                        .unwrap()
+                        .into_iter()
+                        .map(|command| command.path)
+                        .collect();
+                    (crate_name.to_string(), paths)
+                })
+                .collect()
+        };
+
+        let parallel = command_files(4);
+        for (crate_name, paths) in &parallel {
+            let expected: Vec<String> = (0..10)
+                .map(|i| format!("{0}::m{1}::{0}_{1}", crate_name, i))
+                .collect();
+            assert_eq!(paths, &expected);
+        }
+        assert_eq!(command_files(1), parallel);
+    }
+
+    #[test]
+    fn packages_are_scanned_by_name() {
+        let command = |name: &str| {
+            format!(
+                "#[tauri::command]\n#[auto_collect_command]\npub fn {}() {{}}\n",
+                name
+            )
+        };
+        let fixture = Fixture::workspace(&["crates/users", "crates/settings", "crates/admin"])
+            .file(
+                "crates/users/Cargo.toml",
+                "[package]\nname = \"users-api\"\nversion = \"0.1.0\"\n",
+            )
+            .file(
+                "crates/settings/Cargo.toml",
+                "[package]\nname = \"settings\"\nversion = \"0.1.0\"\n",
+            )
+            .file("crates/users/src/lib.rs", &command("load_user"))
+            .file("crates/settings/src/lib.rs", &command("set_theme"))
+            .file("crates/admin/src/lib.rs", &command("ban_user"));
+
+        let options = TauriHelperOptions::builder()
+            .scan_package("users-api")
+            .scan_package("settings")
+            .scan_package("billing")
+            .build();
+        let scan = fixture.scan(&options);
+        assert_eq!(
+            fixture.command_paths(&options),
+            // commands are still named after the directory of their crate
+            ["settings::set_theme", "users::load_user"]
+        );
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [
+                "package `billing` of `scan_packages` is not defined by any workspace member, it is not scanned"
+            ]
+        );
+    }
+
+    #[test]
+    fn outputs_are_the_same_wherever_the_workspace_is() {
+        let workspace = || {
+            Fixture::workspace(&["cmds"])
+                .file(
+                    "cmds/src/lib.rs",
+                    r#"
+                pub mod users;
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn greet(name: String) -> String { name }
+                "#,
+                )
+                .file(
+                    "cmds/src/users.rs",
+                    r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "users")]
+                pub fn load_user(id: u32) -> Result<u32, String> { Ok(id) }
+
+                #[tauri::command]
+                pub fn unannotated() {}
+                "#,
+                )
+        };
+        let outputs = [
+            "target/tauri_commands_list/cmds.txt",
+            "commands.keyed.json",
+            "commands.modules.json",
+            "commands.schemas.json",
+            "commands.review.md",
+            "commands.ts",
+            "commands.frontend.ts",
+            "permissions.md",
+            "commands.dot",
+            "commands.h",
+        ];
+        let options = TauriHelperOptions::builder()
+            .keyed_json(outputs[1])
+            .module_index(outputs[2])
+            .return_schemas(outputs[3])
+            .review_list(outputs[4])
+            .ts_bindings(outputs[5])
+            .frontend_commands(outputs[6])
+            .permissions_reference(outputs[7])
+            .dot_graph(outputs[8])
+            .c_header(outputs[9])
+            .build();
+
+        let (first, second) = (workspace(), workspace());
+        write_command_files_at(first.root(), &options);
+        write_command_files_at(second.root(), &options);
+        for output in outputs {
+            let contents = fs::read_to_string(first.root().join(output)).unwrap();
+            assert!(
+                !contents.contains(&*first.root().to_string_lossy()),
+                "{} holds an absolute path:\n{}",
+                output,
+                contents
+            );
+            assert_eq!(
+                contents,
+                fs::read_to_string(second.root().join(output)).unwrap(),
+                "{}",
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn members_written_as_paths_are_scanned() {
+        let fixture = Fixture::workspace(&["./crates/users/"]).file(
+            "crates/users/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+        );
+
+        write_command_files_at(fixture.root(), &TauriHelperOptions::default());
+        let commands = read_command_file(&commands_dir_at(fixture.root()).join("users.txt"));
+        assert_eq!(commands.unwrap()[0].path, "users::greet");
+    }
+
+    #[test]
+    fn skipping_the_collection_keeps_the_command_files() {
+        let fixture = collected();
+        let command_file = commands_dir_at(fixture.root()).join("cmds.txt");
+        let before = fs::read_to_string(&command_file).unwrap();
+        fs::write(
+            fixture.root().join("cmds/src/lib.rs"),
+            "#[tauri::command]\n#[auto_collect_command]\npub fn farewell() {}\n",
+        )
+        .unwrap();
+
+        skip_collection(fixture.root(), &TauriHelperOptions::default());
+        assert_eq!(fs::read_to_string(&command_file).unwrap(), before);
+    }
+
+    #[test]
+    fn many_members_are_scanned_with_few_threads() {
+        let members: Vec<String> = (0..64).map(|i| format!("member_{}", i)).collect();
+        let members: Vec<&str> = members.iter().map(String::as_str).collect();
+        let mut fixture = Fixture::workspace(&members);
+        for (i, member) in members.iter().enumerate() {
+            fixture = fixture
+                .file(
+                    &format!("{}/src/lib.rs", member),
+                    &format!(
+                        "pub mod nested;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn command_{}() {{}}\n",
+                        i
+                    ),
+                )
+                .file(
+                    &format!("{}/src/nested.rs", member),
+                    &format!(
+                        "#[tauri::command]\n#[auto_collect_command]\npub fn nested_{}() {{}}\n",
+                        i
+                    ),
+                );
+        }
+
+        let paths = fixture.command_paths(&TauriHelperOptions::builder().scan_threads(2).build());
+        assert_eq!(paths.len(), 128);
+        assert_eq!(paths, fixture.command_paths(&TauriHelperOptions::default()));
+    }
+
+    #[test]
+    fn command_names_are_written_into_the_frontend() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(alias = "get_user")]
+            pub fn load_user() {}
+            "#,
+        );
+        let options = TauriHelperOptions::builder()
+            .frontend_commands("frontend/src/generated/commands.ts")
+            .build();
+        write_command_files_at(fixture.root(), &options);
+
+        let names =
+            fs::read_to_string(fixture.root().join("frontend/src/generated/commands.ts")).unwrap();
+        assert!(names.contains(
+            "export const COMMANDS = [\"get_user\", \"greet\", \"load_user\"] as const;\n"
+        ));
+        assert!(names.contains("export type CommandName = (typeof COMMANDS)[number];\n"));
+    }
+
+    #[test]
+    fn command_files_start_with_the_header() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+        );
+        let options = TauriHelperOptions::builder()
+            .command_file_header(true)
+            .build();
+        write_command_files_at(fixture.root(), &options);
+
+        let command_file = commands_dir_at(fixture.root()).join("cmds.txt");
+        assert!(
+            fs::read_to_string(&command_file)
+                .unwrap()
+                .starts_with("# generated by tauri-helper for crate cmds\n")
+        );
+        let commands = read_command_file(&command_file).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].path, "cmds::greet");
+    }
+
+    #[test]
+    fn appended_commands_are_collected_once() {
+        let fixture = collected();
+        let commands_dir = commands_dir_at(fixture.root());
+        append_command_at(&commands_dir, "cmds", "cmds::generated::ping");
+        append_command_at(&commands_dir, "cmds", "cmds::generated::ping");
+        append_command_at(&commands_dir, "generated", "generated::pong");
+
+        let paths: Vec<String> = discover_commands(&commands_dir)
+            .unwrap()
+            .into_iter()
+            .map(|command| command.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "cmds::greet",
+                "cmds::load_user",
+                "cmds::generated::ping",
+                "generated::pong"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid command path `ping`")]
+    fn appended_commands_need_a_full_path() {
+        let fixture = collected();
+        append_command_at(&commands_dir_at(fixture.root()), "cmds", "ping");
+    }
+
+    #[test]
+    fn the_fast_scan_keeps_the_outputs_relying_on_signatures() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn load_user(id: u32) -> String { id.to_string() }\n",
+        );
+        let outputs = [
+            "tauri-commands.lock",
+            "commands.ts",
+            "commands.schemas.json",
+        ];
+        let options = || {
+            TauriHelperOptions::builder()
+                .signature_lock(outputs[0])
+                .ts_bindings(outputs[1])
+                .return_schemas(outputs[2])
+        };
+        write_command_files_at(fixture.root(), &options().build());
+        let read =
+            || outputs.map(|output| fs::read_to_string(fixture.root().join(output)).unwrap());
+        let written = read();
+
+        let options = options().fast_scan(true).build();
+        write_command_files_at(fixture.root(), &options);
+        assert_eq!(read(), written);
+        // the pinned signatures aren't compared with the unknown ones
+        assert_eq!(
+            validate::validate_command_files_at(fixture.root(), &options),
+            Ok(())
+        );
+    }
 }
@@ -0,0 +1,53 @@
+use quote::ToTokens;
+use std::collections::BTreeMap;
+
+use crate::scan::WorkspaceScan;
+
+/// Hashes a command signature, the hash changing whenever the signature does.
+///
+/// The signature is normalized by printing its tokens, so formatting and comments don't affect
+/// it. FNV-1a is used rather than the standard hasher, whose output isn't guaranteed to be stable
+/// across Rust versions.
+pub(crate) fn signature_hash(signature: &syn::Signature) -> String {
+    let normalized = signature.to_token_stream().to_string();
+    format!("{:016x}", fnv1a(normalized.bytes()))
+}
+
+/// Hashes bytes with FNV-1a, whose output is the same on every platform and Rust version.
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes
+        .into_iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Generates the signature lock, one line per command sorted by path, holding the path and the
+/// signature hash separated by a tab, after a `# Command signatures pinned by tauri-helper, do not
+/// edit.` header.
+pub(crate) fn generate_lockfile(scan: &WorkspaceScan) -> String {
+    let mut out = String::from("# Command signatures pinned by tauri-helper, do not edit.\n");
+    for (path, hash) in signature_hashes(scan) {
+        out.push_str(&format!("{}\t{}\n", path, hash));
+    }
+    out
+}
+
+/// Returns the signature hash of every scanned command, keyed by path.
+pub(crate) fn signature_hashes(scan: &WorkspaceScan) -> BTreeMap<&str, &str> {
+    scan.members
+        .iter()
+        .flat_map(|member| member.commands.iter().zip(&member.scanned))
+        .map(|(command, scanned)| (command.path.as_str(), scanned.signature_hash.as_str()))
+        .collect()
+}
+
+/// Reads a signature lock, skipping comments and malformed lines.
+pub(crate) fn read_lockfile(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(path, hash)| (path.to_string(), hash.trim().to_string()))
+        .collect()
+}
@@ -0,0 +1,110 @@
+use serde_json::{Map, Value, json};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+use tauri_helper_core::command::CommandInfo;
+
+use crate::scan::WorkspaceScan;
+
+/// Merges the commands of the scanned crates into the consolidated manifest located at `path`,
+/// `{ "crates": { "<crate>": [<command>, ...] } }`.
+///
+/// The entries of crates that weren't scanned are kept as they are, so that members whose build
+/// scripts only scan themselves don't clobber each other. Scanned crates without commands are
+/// removed. The file is locked for the whole read-modify-write, parallel builds merging their
+/// crates one at a time.
+pub(crate) fn merge_manifest(path: &Path, scan: &WorkspaceScan) {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .unwrap_or_else(|e| panic!("Failed to open {}: {}", path.display(), e));
+    file.lock()
+        .unwrap_or_else(|e| panic!("Failed to lock {}: {}", path.display(), e));
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap();
+
+    // an empty or unreadable manifest is rebuilt from the scanned crates
+    let mut crates: Map<String, Value> = serde_json::from_str::<Value>(&contents)
+        .ok()
+        .and_then(|mut manifest| match manifest.get_mut("crates")?.take() {
+            Value::Object(crates) => Some(crates),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let scanned = scan.crates();
+    crates.retain(|crate_name, _| !scanned.contains_key(crate_name.as_str()));
+    crates.extend(
+        scanned
+            .into_iter()
+            .filter(|(_, commands)| !commands.is_empty())
+            .map(|(crate_name, commands)| {
+                let commands = commands.iter().map(CommandInfo::to_json).collect();
+                (crate_name.to_string(), Value::Array(commands))
+            }),
+    );
+    crates.sort_keys();
+
+    let manifest = json!({ "crates": crates });
+    file.set_len(0).unwrap();
+    file.rewind().unwrap();
+    writeln!(file, "{}", serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+    // the lock is released when the file is closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use std::fs;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    fn paths(manifest: &Value, crate_name: &str) -> Vec<String> {
+        manifest["crates"][crate_name]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|command| command["path"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn members_update_their_own_section() {
+        let fixture = Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn set_theme() {}\n",
+            )
+            .file(
+                "users/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            );
+        let manifest = fixture.root().join("_all.json");
+        let scan_member =
+            |member: &str| fixture.scan(&TauriHelperOptions::builder().members([member]).build());
+
+        // each member's build script scans only itself
+        merge_manifest(&manifest, &scan_member("settings"));
+        merge_manifest(&manifest, &scan_member("users"));
+        let merged: Value = serde_json::from_str(&fs::read_to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(paths(&merged, "settings"), ["settings::set_theme"]);
+        assert_eq!(paths(&merged, "users"), ["users::load_user"]);
+
+        // a member updating its commands leaves the other one as it was
+        let fixture = fixture.file(
+            "users/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn save_user() {}\n",
+        );
+        merge_manifest(
+            &manifest,
+            &fixture.scan(&TauriHelperOptions::builder().members(["users"]).build()),
+        );
+        let merged: Value = serde_json::from_str(&fs::read_to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(paths(&merged, "settings"), ["settings::set_theme"]);
+        assert_eq!(paths(&merged, "users"), ["users::save_user"]);
+    }
+}
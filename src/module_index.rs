@@ -0,0 +1,100 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::scan::WorkspaceScan;
+
+/// Generates a JSON object listing the names of the commands defined by each module, keyed by the
+/// path of the module:
+///
+/// ```json
+/// {
+///   "my_app": ["greet"],
+///   "my_app::settings": ["load_settings", "save_settings"]
+/// }
+/// ```
+///
+/// Unlike groups, which are set by hand, the modules reflect where the commands are defined. The
+/// defining module is used even for commands re-exported elsewhere.
+pub(crate) fn generate_module_index(scan: &WorkspaceScan) -> String {
+    let mut modules: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for command in scan.commands() {
+        let crate_name = command.path.split("::").next().unwrap_or_default();
+        let module = match &command.module {
+            Some(module) => format!("{}::{}", crate_name, module),
+            None => crate_name.to_string(),
+        };
+        modules
+            .entry(module)
+            .or_default()
+            .push(command.exposed_name());
+    }
+
+    let object: Map<String, Value> = modules
+        .into_iter()
+        .map(|(module, mut names)| {
+            names.sort_unstable();
+            (module, names.into())
+        })
+        .collect();
+    serde_json::to_string_pretty(&object).unwrap() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use serde_json::json;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn commands_are_grouped_under_their_defining_module() {
+        let fixture = Fixture::workspace(&["app"])
+            .file(
+                "app/src/lib.rs",
+                r#"
+                pub mod settings;
+                mod internal;
+                pub use internal::reset;
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn greet() {}
+                "#,
+            )
+            .file(
+                "app/src/settings.rs",
+                r#"
+                pub mod theme {
+                    #[tauri::command]
+                    #[auto_collect_command]
+                    pub fn set_theme() {}
+                }
+
+                #[tauri::command]
+                #[auto_collect_command(group = "storage")]
+                pub fn save_settings() {}
+
+                #[tauri::command]
+                #[auto_collect_command(rename = "get_settings")]
+                pub fn load_settings() {}
+                "#,
+            )
+            .file(
+                "app/src/internal.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn reset() {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let index: Value = serde_json::from_str(&generate_module_index(&scan)).unwrap();
+        assert_eq!(
+            index,
+            json!({
+                "app": ["greet"],
+                // re-exported from the crate root, but defined here
+                "app::internal": ["reset"],
+                "app::settings": ["get_settings", "save_settings"],
+                "app::settings::theme": ["set_theme"]
+            })
+        );
+    }
+}
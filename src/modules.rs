@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{Expr, Lit, Meta, parse_file};
+use tauri_helper_core::get_member_manifest;
+
+use crate::reexports::ReexportGraph;
+
+/// Module a source file is loaded as.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModuleInfo {
+    /// Path of the module from the crate root, empty for the crate root itself.
+    pub path: Vec<String>,
+    /// Whether the module, or one of its ancestors, is gated with `#[cfg(test)]`.
+    pub test_only: bool,
+    /// `#[cfg(...)]` predicates on the module or one of its ancestors.
+    pub cfg: Vec<String>,
+}
+
+/// Returns the crate roots of a member: its `[lib]` and `[[bin]]` targets, honoring the paths
+/// set in its manifest.
+pub(crate) fn crate_roots(manifest_dir: &Path) -> Vec<PathBuf> {
+    let manifest = get_member_manifest(manifest_dir);
+    let mut roots = vec![
+        manifest_dir.join(
+            manifest
+                .lib
+                .and_then(|lib| lib.path)
+                .unwrap_or_else(|| "src/lib.rs".to_string()),
+        ),
+    ];
+
+    if manifest.bin.is_empty() {
+        roots.push(manifest_dir.join("src/main.rs"));
+    }
+    for bin in manifest.bin {
+        let path = match (bin.path, bin.name) {
+            (Some(path), _) => path,
+            (None, Some(name)) if manifest_dir.join(format!("src/bin/{}.rs", name)).exists() => {
+                format!("src/bin/{}.rs", name)
+            }
+            _ => "src/main.rs".to_string(),
+        };
+        roots.push(manifest_dir.join(path));
+    }
+
+    roots.retain(|root| root.is_file());
+    roots.dedup();
+    roots
+}
+
+/// Returns the directories holding the sources of a member: `src` and the directories of its
+/// crate roots, e.g. `rust` for `[lib] path = "rust/lib.rs"`, directories nested in another one
+/// being dropped.
+///
+/// Only subdirectories of the manifest directory are included, the manifest directory itself
+/// holds the build script and the build outputs.
+pub(crate) fn source_dirs(manifest_dir: &Path, roots: &[PathBuf]) -> Vec<PathBuf> {
+    let manifest = normalize(manifest_dir);
+    let mut dirs: Vec<PathBuf> = std::iter::once(manifest_dir.join("src"))
+        .chain(
+            roots
+                .iter()
+                .filter_map(|root| root.parent())
+                .map(Path::to_path_buf),
+        )
+        .filter(|dir| {
+            let dir = normalize(dir);
+            dir != manifest && dir.starts_with(&manifest)
+        })
+        .collect();
+    dirs.sort();
+    dirs.dedup_by_key(|dir| normalize(dir));
+
+    let normalized: Vec<PathBuf> = dirs.iter().map(|dir| normalize(dir)).collect();
+    dirs.into_iter()
+        .zip(&normalized)
+        .filter(|(_, dir)| {
+            !normalized
+                .iter()
+                .any(|other| other != *dir && dir.starts_with(other))
+        })
+        .map(|(dir, _)| dir)
+        .collect()
+}
+
+/// Maps every source file reachable from the crate roots of a member to the module it is loaded
+/// as, following `mod` declarations and their `#[path]` attributes.
+///
+/// The modules and `pub use` declarations found along the way are recorded in the returned graph.
+pub(crate) fn module_map(roots: &[PathBuf]) -> (HashMap<PathBuf, ModuleInfo>, ReexportGraph) {
+    let mut map = HashMap::new();
+    let mut graph = ReexportGraph::default();
+    for root in roots {
+        walk_file(root, ModuleInfo::default(), true, &mut map, &mut graph);
+    }
+    (map, graph)
+}
+
+/// Returns the module of a file that isn't reachable from a crate root, guessed from its location
+/// relative to `src_dir`, one of the [`source_dirs`] of its member.
+pub(crate) fn module_from_location(src_dir: &Path, file: &Path) -> ModuleInfo {
+    let mut path: Vec<String> = file
+        .strip_prefix(src_dir)
+        .unwrap_or(file)
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+
+    if path.last().is_some_and(|last| last == "mod")
+        || (path.len() == 1 && (path[0] == "lib" || path[0] == "main"))
+    {
+        path.pop();
+    }
+
+    ModuleInfo {
+        path,
+        ..Default::default()
+    }
+}
+
+/// Normalizes a path so that files found through the module tree and through the directory walk
+/// can be matched.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn walk_file(
+    file: &Path,
+    module: ModuleInfo,
+    mod_rs: bool,
+    map: &mut HashMap<PathBuf, ModuleInfo>,
+    graph: &mut ReexportGraph,
+) {
+    let key = normalize(file);
+    if map.contains_key(&key) {
+        return;
+    }
+
+    if !file.is_file() {
+        return;
+    }
+    // unparsable files are kept so that the scanner reports them
+    map.insert(key, module.clone());
+    let Some(ast) = fs::read_to_string(file)
+        .ok()
+        .and_then(|content| parse_file(&content).ok())
+    else {
+        return;
+    };
+
+    let dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+    // `foo.rs` declares its submodules in `foo/`, `mod.rs` and crate roots next to themselves
+    let child_dir = if mod_rs {
+        dir.clone()
+    } else {
+        dir.join(file.file_stem().unwrap_or_default())
+    };
+
+    walk_items(&ast.items, &module, &dir, &child_dir, map, graph);
+}
+
+fn walk_items(
+    items: &[syn::Item],
+    module: &ModuleInfo,
+    path_dir: &Path,
+    child_dir: &Path,
+    map: &mut HashMap<PathBuf, ModuleInfo>,
+    graph: &mut ReexportGraph,
+) {
+    graph.add_items(&module.path, items);
+    for item in items {
+        let syn::Item::Mod(item_mod) = item else {
+            continue;
+        };
+
+        let name = item_mod.ident.to_string();
+        let file_name = name.trim_start_matches("r#");
+        let path_attr = path_attribute(&item_mod.attrs);
+
+        let mut child = module.clone();
+        child.path.push(name.clone());
+        child.test_only |= item_mod.attrs.iter().any(crate::scan::is_cfg_test);
+        child
+            .cfg
+            .extend(crate::scan::cfg_predicates(&item_mod.attrs));
+        graph.add_module(
+            child.path.clone(),
+            matches!(item_mod.vis, syn::Visibility::Public(_)),
+        );
+
+        match &item_mod.content {
+            Some((_, items)) => {
+                let dir = match &path_attr {
+                    Some(path) => path_dir.join(path),
+                    None => child_dir.join(file_name),
+                };
+                walk_items(items, &child, &dir, &dir, map, graph);
+            }
+            None => match path_attr {
+                // files loaded through `#[path]` behave like `mod.rs` files
+                Some(path) => walk_file(&path_dir.join(path), child, true, map, graph),
+                None => {
+                    let file = child_dir.join(format!("{}.rs", file_name));
+                    if file.is_file() {
+                        walk_file(&file, child, false, map, graph);
+                    } else {
+                        walk_file(
+                            &child_dir.join(file_name).join("mod.rs"),
+                            child,
+                            true,
+                            map,
+                            graph,
+                        );
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Returns the value of a `#[path = "..."]` attribute.
+fn path_attribute(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(nv) if nv.path.is_ident("path") => match &nv.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(value) => Some(value.value()),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
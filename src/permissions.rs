@@ -0,0 +1,81 @@
+use serde_json::{Map, Value, json};
+
+use crate::scan::WorkspaceScan;
+
+/// Generates a JSON object mapping every name a command can be invoked with, aliases included, to
+/// the identifiers of the permissions Tauri generates for it:
+///
+/// ```json
+/// {
+///   "load_user": { "allow": "allow-load-user", "deny": "deny-load-user" }
+/// }
+/// ```
+pub(crate) fn generate_permissions_reference(scan: &WorkspaceScan) -> String {
+    let mut names: Vec<&str> = scan
+        .commands()
+        .filter(|command| !command.metadata_only)
+        .flat_map(|command| {
+            std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(String::as_str))
+        })
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let object: Map<String, Value> = names
+        .into_iter()
+        .map(|name| {
+            // Tauri derives the identifiers from the command name with `-` instead of `_`
+            let identifier = name.trim_start_matches("r#").replace('_', "-");
+            let permissions = json!({
+                "allow": format!("allow-{}", identifier),
+                "deny": format!("deny-{}", identifier),
+            });
+            (name.to_string(), permissions)
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&object).unwrap() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use serde_json::json;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn every_command_has_its_permissions() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(alias = "get_user")]
+            pub fn load_user() {}
+
+            #[tauri::command]
+            #[auto_collect_command(rename = "save")]
+            pub fn save_settings() {}
+            "#,
+        );
+
+        let reference: serde_json::Value = serde_json::from_str(&generate_permissions_reference(
+            &fixture.scan(&TauriHelperOptions::default()),
+        ))
+        .unwrap();
+        assert_eq!(
+            reference,
+            json!({
+                "get_user": { "allow": "allow-get-user", "deny": "deny-get-user" },
+                "greet": { "allow": "allow-greet", "deny": "deny-greet" },
+                "load_user": { "allow": "allow-load-user", "deny": "deny-load-user" },
+                "save": { "allow": "allow-save", "deny": "deny-save" },
+            })
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tauri_helper_core::args::CommandArgs;
+use tauri_helper_core::command::CommandInfo;
+
+use crate::scan::{MemberScan, ScannedCommand, WorkspaceScan};
+use crate::signature::CommandSignature;
+
+/// Reads the commands of a precompiled artifact, see `TauriHelperOptions::precompiled_commands`,
+/// as if they had been found by a scan of the workspace located in `workspace_root`.
+///
+/// The artifact only holds the command file entries, the signatures are unknown.
+///
+/// # Panics
+///
+/// Panics if the artifact can't be read or isn't a valid `{ "crates": { ... } }` object.
+pub(crate) fn read_precompiled(workspace_root: &Path, artifact: &Path) -> WorkspaceScan {
+    let members = read_artifact(artifact)
+        .into_iter()
+        .map(|(crate_name, commands)| MemberScan {
+            members: Vec::new(),
+            crate_name,
+            files_scanned: 0,
+            scanned: commands.iter().map(scanned_command).collect(),
+            commands,
+            warnings: Vec::new(),
+            unannotated: Vec::new(),
+            structs: Vec::new(),
+        })
+        .collect();
+
+    WorkspaceScan {
+        workspace_root: workspace_root.to_path_buf(),
+        members,
+        warnings: Vec::new(),
+        elapsed: Duration::ZERO,
+        files_cached: 0,
+        signatures_known: false,
+    }
+}
+
+/// Fails the build if the commands of the artifact differ from the ones found in the sources.
+pub(crate) fn check_precompiled(artifact: &Path, scan: &WorkspaceScan) {
+    let entries = |crates: Vec<(&str, &[CommandInfo])>| -> BTreeMap<(String, String), String> {
+        crates
+            .into_iter()
+            .flat_map(|(crate_name, commands)| {
+                commands.iter().map(move |command| {
+                    (
+                        (crate_name.to_string(), command.path.clone()),
+                        command.to_json().to_string(),
+                    )
+                })
+            })
+            .collect()
+    };
+
+    let precompiled = read_artifact(artifact);
+    let expected = entries(
+        precompiled
+            .iter()
+            .map(|(crate_name, commands)| (crate_name.as_str(), commands.as_slice()))
+            .collect(),
+    );
+    let found = entries(scan.crates().into_iter().collect());
+
+    let mut mismatches = Vec::new();
+    for ((crate_name, path), command) in &found {
+        match expected.get(&(crate_name.clone(), path.clone())) {
+            None => mismatches.push(format!(
+                "{}: `{}` is missing from the artifact",
+                crate_name, path
+            )),
+            Some(previous) if previous != command => mismatches.push(format!(
+                "{}: `{}` differs, found {} instead of {}",
+                crate_name, path, command, previous
+            )),
+            Some(_) => {}
+        }
+    }
+    for (crate_name, path) in expected.keys() {
+        if !found.contains_key(&(crate_name.clone(), path.clone())) {
+            mismatches.push(format!(
+                "{}: `{}` is no longer in the sources",
+                crate_name, path
+            ));
+        }
+    }
+
+    if !mismatches.is_empty() {
+        panic!(
+            "The precompiled commands {} don't match the sources:\n  {}",
+            artifact.display(),
+            mismatches.join("\n  ")
+        );
+    }
+}
+
+/// Reads the commands of each crate of an artifact.
+fn read_artifact(artifact: &Path) -> Vec<(String, Vec<CommandInfo>)> {
+    let contents = fs::read_to_string(artifact).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read the precompiled commands {}: {}",
+            artifact.display(),
+            e
+        )
+    });
+    let json: Value = serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Invalid precompiled commands {}: {}", artifact.display(), e));
+    let crates = json
+        .get("crates")
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| {
+            panic!(
+                "Invalid precompiled commands {}: no `crates` object",
+                artifact.display()
+            )
+        });
+
+    crates
+        .iter()
+        .map(|(crate_name, commands)| {
+            let commands = commands
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|command| {
+                    CommandInfo::from_json(command).unwrap_or_else(|| {
+                        panic!(
+                            "Invalid precompiled commands {}: a command of `{}` has no path",
+                            artifact.display(),
+                            crate_name
+                        )
+                    })
+                })
+                .collect();
+            (crate_name.clone(), commands)
+        })
+        .collect()
+}
+
+/// Rebuilds the scanned form of a command from its command file entry.
+fn scanned_command(command: &CommandInfo) -> ScannedCommand {
+    ScannedCommand {
+        name: command
+            .path_in_crate()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        module: command
+            .module
+            .iter()
+            .flat_map(|module| module.split("::"))
+            .map(str::to_string)
+            .collect(),
+        cfg: command.cfg.clone(),
+        args: CommandArgs {
+            platform: command.platform.clone(),
+            group: command.group.clone(),
+            rename: command.name.clone(),
+            aliases: command.aliases.clone(),
+            timeout_ms: command.timeout_ms,
+        },
+        signature: CommandSignature::default(),
+        signature_hash: String::new(),
+        public: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use crate::write_command_files_at;
+    use tauri_helper_core::command::read_command_file;
+    use tauri_helper_core::commands_dir_at;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    const GREET: &str = "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n";
+
+    /// Returns the paths of the commands written for the `cmds` crate.
+    fn written_paths(fixture: &Fixture) -> Vec<String> {
+        read_command_file(&commands_dir_at(fixture.root()).join("cmds.txt"))
+            .unwrap()
+            .into_iter()
+            .map(|command| command.path)
+            .collect()
+    }
+
+    /// A workspace whose commands were collected into a `commands.json` artifact, as by an earlier
+    /// CI step.
+    fn precompiled_fixture() -> Fixture {
+        let fixture = Fixture::workspace(&["cmds"]).file("cmds/src/lib.rs", GREET);
+        write_command_files_at(fixture.root(), &TauriHelperOptions::default());
+        let manifest = fs::read_to_string(commands_dir_at(fixture.root()).join("_all.json"));
+        fixture.file("commands.json", &manifest.unwrap())
+    }
+
+    fn check_options() -> TauriHelperOptions {
+        TauriHelperOptions::builder()
+            .precompiled_commands("commands.json")
+            .check_precompiled(true)
+            .build()
+    }
+
+    #[test]
+    fn artifacts_are_read_instead_of_the_sources() {
+        let fixture = precompiled_fixture().file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn farewell() {}\n",
+        );
+        let options = TauriHelperOptions::builder()
+            .precompiled_commands("commands.json")
+            .build();
+
+        write_command_files_at(fixture.root(), &options);
+        assert_eq!(written_paths(&fixture), ["cmds::greet"]);
+    }
+
+    #[test]
+    fn matching_artifacts_pass_the_check() {
+        let fixture = precompiled_fixture();
+
+        write_command_files_at(fixture.root(), &check_options());
+        assert_eq!(written_paths(&fixture), ["cmds::greet"]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "don't match the sources:\n  cmds: `cmds::farewell` is missing from the artifact\n  cmds: `cmds::greet` is no longer in the sources"
+    )]
+    fn mismatching_artifacts_fail_the_check() {
+        let fixture = precompiled_fixture().file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn farewell() {}\n",
+        );
+
+        write_command_files_at(fixture.root(), &check_options());
+    }
+
+    #[test]
+    fn committed_signature_locks_survive_precompiled_builds() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn load_user(id: u32) {}\n",
+        );
+        let lock = fixture.root().join("tauri-commands.lock");
+        let options = TauriHelperOptions::builder().signature_lock("tauri-commands.lock");
+        write_command_files_at(fixture.root(), &options.build());
+        let pinned = fs::read_to_string(&lock).unwrap();
+        let manifest = fs::read_to_string(commands_dir_at(fixture.root()).join("_all.json"));
+        let fixture = fixture.file("commands.json", &manifest.unwrap());
+
+        let options = TauriHelperOptions::builder()
+            .signature_lock("tauri-commands.lock")
+            .precompiled_commands("commands.json")
+            .build();
+        write_command_files_at(fixture.root(), &options);
+        assert_eq!(written_paths(&fixture), ["cmds::load_user"]);
+        assert_eq!(fs::read_to_string(&lock).unwrap(), pinned);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+/// The module tree of a crate along with its `pub use` declarations, used to find the path other
+/// crates can reach a command through.
+#[derive(Debug, Default)]
+pub(crate) struct ReexportGraph {
+    /// Every module of the crate, the crate root excluded.
+    modules: HashSet<Vec<String>>,
+    /// Modules declared `pub`.
+    public_modules: HashSet<Vec<String>>,
+    reexports: Vec<Reexport>,
+}
+
+/// An item re-exported by a `pub use` declaration.
+#[derive(Debug)]
+struct Reexport {
+    /// Module declaring the `use`.
+    module: Vec<String>,
+    /// Path of the module the item is imported from, as written.
+    source: Vec<String>,
+    /// Name of the imported item, `None` for a glob.
+    item: Option<String>,
+}
+
+impl ReexportGraph {
+    /// Records a module declared in the crate.
+    pub(crate) fn add_module(&mut self, path: Vec<String>, public: bool) {
+        if public {
+            self.public_modules.insert(path.clone());
+        }
+        self.modules.insert(path);
+    }
+
+    /// Records the `pub use` declarations among the items of a module.
+    pub(crate) fn add_items(&mut self, module: &[String], items: &[syn::Item]) {
+        for item in items {
+            if let syn::Item::Use(item_use) = item
+                && matches!(item_use.vis, syn::Visibility::Public(_))
+                // `pub use ::other_crate::...` never refers to this crate
+                && item_use.leading_colon.is_none()
+            {
+                self.add_tree(module, Vec::new(), &item_use.tree);
+            }
+        }
+    }
+
+    fn add_tree(&mut self, module: &[String], mut source: Vec<String>, tree: &syn::UseTree) {
+        let item = match tree {
+            syn::UseTree::Path(path) => {
+                source.push(path.ident.to_string());
+                return self.add_tree(module, source, &path.tree);
+            }
+            syn::UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.add_tree(module, source.clone(), tree);
+                }
+                return;
+            }
+            // only a route to the command along with its `__cmd__` macro, see `public_path`
+            syn::UseTree::Name(name) => Some(name.ident.to_string()),
+            // Tauri registers a command under the name of its function and looks for the
+            // `__cmd__` macro next to it, a renamed re-export can't be registered
+            syn::UseTree::Rename(_) => return,
+            syn::UseTree::Glob(_) => None,
+        };
+        self.reexports.push(Reexport {
+            module: module.to_vec(),
+            source,
+            item,
+        });
+    }
+
+    /// Returns the shortest path, from the crate root, through which other crates can reach the
+    /// public function `name` defined in `module`, either directly or through `pub use`
+    /// re-exports. Returns `None` if the function isn't reachable from outside of the crate.
+    pub(crate) fn public_path(&self, module: &[String], name: &str) -> Option<Vec<String>> {
+        // every module the function can be referred to from
+        let mut modules: Vec<Vec<String>> = vec![module.to_vec()];
+        let mut i = 0;
+        while i < modules.len() {
+            let from = modules[i].clone();
+            for reexport in &self.reexports {
+                if reexport.item.as_deref().is_some_and(|item| item != name)
+                    // `generate_handler!` refers to the `__cmd__` macro next to the function, which
+                    // a re-export of the function alone doesn't bring along
+                    || (reexport.item.is_some() && !self.reexports_macro(reexport, name))
+                    || modules.contains(&reexport.module)
+                    || self.resolve(&reexport.module, &reexport.source).as_ref() != Some(&from)
+                {
+                    continue;
+                }
+                modules.push(reexport.module.clone());
+            }
+            i += 1;
+        }
+
+        modules
+            .into_iter()
+            .filter(|module| self.is_public(module))
+            // the first one found is the definition itself, kept on ties
+            .min_by_key(Vec::len)
+            .map(|mut module| {
+                module.push(name.to_string());
+                module
+            })
+    }
+
+    /// Returns `true` if the `__cmd__` macro of the command `name` is re-exported from the same
+    /// module as the named re-export of its function.
+    fn reexports_macro(&self, reexport: &Reexport, name: &str) -> bool {
+        let macro_name = format!("__cmd__{}", name);
+        self.reexports.iter().any(|other| {
+            other.module == reexport.module
+                && other.source == reexport.source
+                && other.item.as_deref() == Some(&macro_name)
+        })
+    }
+
+    /// Returns `true` if the module can be reached from outside of the crate.
+    fn is_public(&self, module: &[String]) -> bool {
+        (1..=module.len()).all(|len| self.public_modules.contains(&module[..len]))
+    }
+
+    /// Resolves the path of a module written in a `use` declaration of `module`, `None` if it
+    /// doesn't refer to a module of this crate.
+    fn resolve(&self, module: &[String], path: &[String]) -> Option<Vec<String>> {
+        let (mut resolved, rest) = match path.first().map(String::as_str) {
+            Some("crate") => (Vec::new(), &path[1..]),
+            Some("self") => (module.to_vec(), &path[1..]),
+            Some("super") => {
+                let mut resolved = module.to_vec();
+                let supers = path
+                    .iter()
+                    .take_while(|segment| *segment == "super")
+                    .count();
+                for _ in 0..supers {
+                    resolved.pop()?;
+                }
+                (resolved, &path[supers..])
+            }
+            // a name that isn't a child module is an external crate
+            _ => (module.to_vec(), path),
+        };
+        resolved.extend(rest.iter().cloned());
+        (resolved.is_empty() || self.modules.contains(&resolved)).then_some(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> Vec<String> {
+        path.split("::").map(str::to_string).collect()
+    }
+
+    fn graph(modules: &[(&str, bool)], uses: &[(&str, &str)]) -> ReexportGraph {
+        let mut graph = ReexportGraph::default();
+        for (module, public) in modules {
+            graph.add_module(path(module), *public);
+        }
+        for (module, item) in uses {
+            let module = if module.is_empty() {
+                Vec::new()
+            } else {
+                path(module)
+            };
+            let item: syn::Item = syn::parse_str(item).unwrap();
+            graph.add_items(&module, &[item]);
+        }
+        graph
+    }
+
+    #[test]
+    fn glob_reexport_of_an_internal_module_is_preferred() {
+        let graph = graph(
+            &[("internal", false), ("internal::commands", true)],
+            &[("", "pub use internal::commands::*;")],
+        );
+        assert_eq!(
+            graph.public_path(&path("internal::commands"), "greet"),
+            Some(path("greet"))
+        );
+    }
+
+    #[test]
+    fn shortest_public_path_wins() {
+        let graph = graph(
+            &[("api", true), ("api::users", true)],
+            &[("", "pub use api::users::{greet, __cmd__greet};")],
+        );
+        assert_eq!(
+            graph.public_path(&path("api::users"), "greet"),
+            Some(path("greet"))
+        );
+    }
+
+    #[test]
+    fn named_reexports_without_the_command_macro_are_ignored() {
+        let graph = graph(
+            &[("api", true), ("api::users", true)],
+            &[("", "pub use api::users::greet;")],
+        );
+        assert_eq!(
+            graph.public_path(&path("api::users"), "greet"),
+            Some(path("api::users::greet"))
+        );
+    }
+
+    #[test]
+    fn private_modules_without_reexport_are_unreachable() {
+        let graph = graph(&[("internal", false)], &[]);
+        assert_eq!(graph.public_path(&path("internal"), "greet"), None);
+    }
+}
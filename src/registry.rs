@@ -0,0 +1,95 @@
+use tauri_helper_core::command::CommandInfo;
+
+/// The files written into `OUT_DIR`, they hold no commands and aren't looked into when included.
+pub(crate) const OUT_DIR_FILES: [&str; 3] = [
+    "tauri_commands.rs",
+    "tauri_builder.rs",
+    "tauri_command_stubs.rs",
+];
+
+/// The `tauri_builder.rs` file written into `OUT_DIR` when `builder_snippet` is set.
+///
+/// The handler is generated where the file is included, from the crate of the application, as if
+/// the snippet was written by hand.
+pub(crate) const BUILDER_SNIPPET: &str = "// Generated by tauri-helper, do not edit.
+
+/// Returns a `tauri::Builder` with every command collected by tauri-helper registered.
+pub fn tauri_builder() -> tauri::Builder<tauri::Wry> {
+    tauri::Builder::default().invoke_handler(tauri_helper::tauri_collect_commands!())
+}
+";
+
+/// Generates the `tauri_commands.rs` module written into `OUT_DIR`.
+///
+/// Commands are sorted by exposed name so the module doesn't change between builds. Commands gated
+/// by `#[cfg(...)]` are listed under the same attributes, a `#[cfg(not(test))]` command is left
+/// out of test builds like it is from the handler.
+pub(crate) fn generate_registry(commands: &[CommandInfo]) -> String {
+    let mut commands = commands.iter().collect::<Vec<_>>();
+    commands.sort_by(|a, b| {
+        a.exposed_name()
+            .cmp(b.exposed_name())
+            .then(a.path.cmp(&b.path))
+    });
+
+    let mut out = String::from(
+        "// Generated by tauri-helper, do not edit.
+
+/// Metadata of a command collected by tauri-helper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandMeta {
+    /// Name the command is registered under.
+    pub name: &'static str,
+    /// Path of the function implementing the command.
+    pub path: &'static str,
+    /// Group of the command, empty if it isn't part of any group.
+    pub group: &'static str,
+}
+
+/// Every command collected by tauri-helper.
+pub const COMMANDS: &[CommandMeta] = &[
+",
+    );
+
+    for command in commands {
+        // the same cfg the handler registers the command under, e.g. `not(test)`
+        for cfg in &command.cfg {
+            out.push_str(&format!("    #[cfg({})]\n", cfg));
+        }
+        out.push_str(&format!(
+            "    CommandMeta {{ name: {:?}, path: {:?}, group: {:?} }},\n",
+            command.exposed_name(),
+            command.path,
+            command.group.as_deref().unwrap_or_default()
+        ));
+    }
+
+    out.push_str("];\n");
+    out
+}
+
+/// Generates the `tauri_command_stubs.rs` test written into `OUT_DIR` when `test_stub` is set.
+///
+/// The test refers to every registered command without calling it, so that it stops compiling
+/// when a command no longer resolves, e.g. after a rename or a move. `commands` must hold the
+/// paths as seen from the crate including the test, its own commands starting with `crate::`.
+pub(crate) fn generate_test_stub<'a>(commands: impl Iterator<Item = &'a CommandInfo>) -> String {
+    let mut out = String::from(
+        "// Generated by tauri-helper, do not edit.
+
+/// Refers to every command collected by tauri-helper.
+#[test]
+fn tauri_commands_resolve() {
+",
+    );
+
+    for command in commands.filter(|command| !command.metadata_only) {
+        for cfg in &command.cfg {
+            out.push_str(&format!("    #[cfg({})]\n", cfg));
+        }
+        out.push_str(&format!("    let _ = {};\n", command.path));
+    }
+
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,193 @@
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+use crate::scan::WorkspaceScan;
+use crate::signature::{RustType, StructDef};
+
+/// Generates a JSON object mapping the exposed name of every command to a JSON Schema of the value
+/// it resolves with, `Result` return types being unwrapped to their `Ok` type:
+///
+/// ```json
+/// {
+///   "load_user": {
+///     "type": "object",
+///     "properties": { "id": { "type": "integer" }, "name": { "type": "string" } },
+///     "required": ["id", "name"]
+///   }
+/// }
+/// ```
+///
+/// The mapping is best-effort: structs with named fields defined anywhere in the workspace are
+/// described by their fields, looked up by name, along with primitives, collections, options and
+/// tuples. Every other type, or a struct name defined more than once, falls back to `{}`.
+pub(crate) fn generate_return_schemas(scan: &WorkspaceScan) -> String {
+    let mut structs: HashMap<&str, Option<&StructDef>> = HashMap::new();
+    for def in scan.members.iter().flat_map(|member| &member.structs) {
+        structs
+            .entry(&def.name)
+            .and_modify(|known| *known = None)
+            .or_insert(Some(def));
+    }
+
+    let mut schemas: Vec<(&str, Value)> = scan
+        .members
+        .iter()
+        .flat_map(|member| member.commands.iter().zip(&member.scanned))
+        .filter(|(info, _)| !info.metadata_only)
+        .map(|(info, command)| {
+            let schema = match &command.signature.output {
+                Some(output) => schema(output.split_result().0, &structs, &mut Vec::new()),
+                None => null(),
+            };
+            (info.exposed_name(), schema)
+        })
+        .collect();
+    schemas.sort_by(|a, b| a.0.cmp(b.0));
+
+    let schemas: Map<String, Value> = schemas
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    serde_json::to_string_pretty(&schemas).unwrap() + "\n"
+}
+
+fn typed(name: &str) -> Value {
+    json!({ "type": name })
+}
+
+fn null() -> Value {
+    typed("null")
+}
+
+/// Returns the schema of a type, `visiting` holding the structs being described to stop at
+/// recursive types.
+fn schema<'a>(
+    ty: &'a RustType,
+    structs: &HashMap<&str, Option<&'a StructDef>>,
+    visiting: &mut Vec<&'a str>,
+) -> Value {
+    match ty.dereferenced() {
+        RustType::Path { name, generics } => match (name.as_str(), generics.as_slice()) {
+            ("String" | "str" | "char" | "PathBuf" | "Path", _) => typed("string"),
+            (
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize",
+                _,
+            ) => typed("integer"),
+            ("f32" | "f64", _) => typed("number"),
+            ("bool", _) => typed("boolean"),
+            ("Option", [inner]) => {
+                let any_of = vec![schema(inner, structs, visiting), null()];
+                json!({ "anyOf": any_of })
+            }
+            ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+                json!({ "type": "array", "items": schema(inner, structs, visiting) })
+            }
+            ("HashMap" | "BTreeMap", [_, value]) => json!({
+                "type": "object",
+                "additionalProperties": schema(value, structs, visiting),
+            }),
+            ("Box" | "Rc" | "Arc" | "Cow", [inner]) => schema(inner, structs, visiting),
+            (name, []) => match structs.get(name) {
+                Some(Some(def)) if !visiting.contains(&name) => {
+                    visiting.push(&def.name);
+                    let properties: Map<String, Value> = def
+                        .fields
+                        .iter()
+                        .map(|field| (field.name.clone(), schema(&field.ty, structs, visiting)))
+                        .collect();
+                    visiting.pop();
+                    // `None` is serialized as `null`, every field is present
+                    let required: Vec<&str> =
+                        def.fields.iter().map(|field| field.name.as_str()).collect();
+                    json!({ "type": "object", "properties": properties, "required": required })
+                }
+                _ => json!({}),
+            },
+            _ => json!({}),
+        },
+        RustType::Slice(inner) | RustType::Array(inner) => {
+            json!({ "type": "array", "items": schema(inner, structs, visiting) })
+        }
+        RustType::Tuple(items) if items.is_empty() => null(),
+        RustType::Tuple(items) => {
+            let items: Vec<Value> = items
+                .iter()
+                .map(|item| schema(item, structs, visiting))
+                .collect();
+            json!({ "type": "array", "prefixItems": items })
+        }
+        _ => json!({}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn struct_return_types_list_their_fields() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                r#"
+                pub mod models;
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub async fn load_user(id: u32) -> Result<User, String> {
+                    todo!()
+                }
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn list_tags() -> Vec<Tag> {
+                    Vec::new()
+                }
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn save_user(user: User) {}
+                "#,
+            )
+            .file(
+                "cmds/src/models.rs",
+                r#"
+                pub struct User {
+                    pub id: u32,
+                    pub name: String,
+                    pub email: Option<String>,
+                    pub manager: Option<Box<User>>,
+                }
+
+                pub struct Tag(String);
+                "#,
+            );
+
+        // the structs are only collected for the schemas
+        let options = TauriHelperOptions::builder()
+            .return_schemas("schemas.json")
+            .build();
+        let scan = fixture.scan(&options);
+        let schemas: Value = serde_json::from_str(&generate_return_schemas(&scan)).unwrap();
+        assert_eq!(
+            schemas,
+            json!({
+                "list_tags": { "type": "array", "items": {} },
+                "load_user": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "name": { "type": "string" },
+                        "email": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                        "manager": { "anyOf": [{}, { "type": "null" }] }
+                    },
+                    "required": ["id", "name", "email", "manager"]
+                },
+                "save_user": { "type": "null" }
+            })
+        );
+    }
+}
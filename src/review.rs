@@ -0,0 +1,107 @@
+use crate::scan::WorkspaceScan;
+
+/// Generates a human-friendly, sorted list of the commands of every crate, meant to be committed
+/// and reviewed.
+///
+/// The output only depends on the commands, it holds no timestamp or absolute path so that it is
+/// reproducible across machines:
+///
+/// ```text
+/// # Commands collected by tauri-helper, do not edit.
+///
+/// # crate: my_app
+/// my_app::greet as hello
+/// my_app::save_settings [group=settings]
+/// ```
+pub(crate) fn generate_review_list(scan: &WorkspaceScan) -> String {
+    let mut out = String::from("# Commands collected by tauri-helper, do not edit.\n");
+
+    for (crate_name, commands) in scan.crates() {
+        if commands.is_empty() {
+            continue;
+        }
+
+        let mut lines: Vec<String> = commands
+            .iter()
+            .map(|command| {
+                let mut line = command.path.clone();
+                if command.name.is_some() {
+                    line.push_str(&format!(" as {}", command.exposed_name()));
+                }
+                let mut tags = Vec::new();
+                if let Some(platform) = &command.platform {
+                    tags.push(format!("platform={}", platform));
+                }
+                if let Some(group) = &command.group {
+                    tags.push(format!("group={}", group));
+                }
+                for alias in &command.aliases {
+                    tags.push(format!("alias={}", alias));
+                }
+                if command.metadata_only {
+                    tags.push("metadata only".to_string());
+                }
+                if !tags.is_empty() {
+                    line.push_str(&format!(" [{}]", tags.join(", ")));
+                }
+                line
+            })
+            .collect();
+        lines.sort();
+
+        out.push_str(&format!("\n# crate: {}\n", crate_name));
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn review_list_is_sorted_per_crate() {
+        let fixture = Fixture::workspace(&["empty", "settings", "users"])
+            .file("empty/src/lib.rs", "")
+            .file(
+                "settings/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(group = "settings", platform = "desktop")]
+                pub fn set_theme() {}
+                "#,
+            )
+            .file(
+                "users/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn save_user() {}
+
+                #[tauri::command]
+                #[auto_collect_command(rename = "hello", alias = "hi")]
+                pub fn greet() {}
+                "#,
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            generate_review_list(&scan),
+            "# Commands collected by tauri-helper, do not edit.
+
+# crate: settings
+settings::set_theme [platform=desktop, group=settings]
+
+# crate: users
+users::greet as hello [alias=hi]
+users::save_user
+"
+        );
+    }
+}
@@ -0,0 +1,2222 @@
+use quote::ToTokens;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs};
+use syn::punctuated::Punctuated;
+use syn::{Token, parse_file};
+use tauri_helper_core::args::CommandArgs;
+use tauri_helper_core::command::{CommandInfo, is_command_path};
+use tauri_helper_core::get_member_manifest;
+use tauri_helper_core::types::{DuplicatePolicy, NamingConvention, TauriHelperOptions};
+use walkdir::WalkDir;
+
+use crate::cache::ScanCache;
+use crate::fast_scan;
+use crate::lockfile;
+use crate::modules::{self, ModuleInfo};
+use crate::registry;
+use crate::signature::{CommandSignature, StructDef};
+
+/// A command found while scanning a source file.
+pub(crate) struct ScannedCommand {
+    /// Name of the function.
+    pub name: String,
+    /// Path of the module defining the function, empty for the crate root.
+    pub module: Vec<String>,
+    /// `#[cfg(...)]` predicates on the function and its modules.
+    pub cfg: Vec<String>,
+    /// Arguments of its `#[auto_collect_command]` attribute.
+    pub args: CommandArgs,
+    pub signature: CommandSignature,
+    /// Hash of the signature, see [`signature_hash`](crate::lockfile::signature_hash).
+    pub signature_hash: String,
+    /// Whether the function is declared `pub`.
+    pub public: bool,
+}
+
+impl ScannedCommand {
+    /// Builds the command file entry of the command, `path` being its full path.
+    pub(crate) fn to_command_info(&self, path: String) -> CommandInfo {
+        CommandInfo {
+            path,
+            platform: self.args.platform.clone(),
+            group: self.args.group.clone(),
+            name: self.args.rename.clone(),
+            aliases: self.args.aliases.clone(),
+            module: (!self.module.is_empty()).then(|| self.module.join("::")),
+            unreachable: false,
+            cfg: self.cfg.clone(),
+            metadata_only: false,
+            no_args: false,
+            timeout_ms: self.args.timeout_ms,
+            signature: self.signature.tokens.clone(),
+        }
+    }
+}
+
+/// Result of scanning a source file.
+#[derive(Default)]
+pub(crate) struct FileScan {
+    pub commands: Vec<ScannedCommand>,
+    pub warnings: Vec<String>,
+    /// `#[tauri::command]` functions without the marker attribute, with their location.
+    pub unannotated: Vec<String>,
+    /// Files of `OUT_DIR` included by the file.
+    pub includes: Vec<OutDirInclude>,
+    /// Structs defined in the file, only collected for `TauriHelperOptions::return_schemas`.
+    pub structs: Vec<StructDef>,
+}
+
+/// A file generated into `OUT_DIR` and included with
+/// `include!(concat!(env!("OUT_DIR"), "/commands.rs"))`.
+pub(crate) struct OutDirInclude {
+    /// Path of the generated file relative to `OUT_DIR`.
+    pub file: String,
+    /// Module the file is included into.
+    pub module: ModuleInfo,
+    /// File holding the `include!`.
+    pub from: PathBuf,
+}
+
+/// Result of scanning a workspace member.
+pub(crate) struct MemberScan {
+    /// The member as listed in the workspace manifest, several of them when their directories
+    /// share a name, see [`group_by_crate`].
+    pub members: Vec<String>,
+    /// Name of the command file of the member.
+    pub crate_name: String,
+    pub files_scanned: usize,
+    /// Commands as written to the command file.
+    pub commands: Vec<CommandInfo>,
+    /// Commands as found in the source, in the same order as `commands`.
+    pub scanned: Vec<ScannedCommand>,
+    pub warnings: Vec<String>,
+    pub unannotated: Vec<String>,
+    /// Structs defined by the member, see [`FileScan::structs`].
+    pub structs: Vec<StructDef>,
+}
+
+/// Commands found by [`scan_workspace`](crate::scan_workspace).
+pub struct WorkspaceScan {
+    /// Root of the scanned workspace.
+    pub workspace_root: PathBuf,
+    pub(crate) members: Vec<MemberScan>,
+    /// Warnings about the workspace itself rather than one of its members.
+    pub(crate) warnings: Vec<String>,
+    /// Time spent scanning.
+    pub elapsed: Duration,
+    /// Number of files whose scan was read from `TauriHelperOptions::scan_cache`.
+    pub(crate) files_cached: usize,
+    /// Whether the signatures of the commands are known, they aren't with `fast_scan` or when the
+    /// commands are read from a precompiled artifact.
+    pub(crate) signatures_known: bool,
+}
+
+impl WorkspaceScan {
+    /// Returns the commands of each crate, keyed by the name of its command file.
+    pub fn crates(&self) -> BTreeMap<&str, &[CommandInfo]> {
+        self.members
+            .iter()
+            .map(|member| (member.crate_name.as_str(), member.commands.as_slice()))
+            .collect()
+    }
+
+    /// Returns every command found in the workspace.
+    pub fn commands(&self) -> impl Iterator<Item = &CommandInfo> {
+        self.members.iter().flat_map(|member| &member.commands)
+    }
+
+    /// Returns the commands as a JSON object, `{ "crates": { "<crate>": [<command>, ...] } }`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let crates: serde_json::Map<String, serde_json::Value> = self
+            .crates()
+            .into_iter()
+            .map(|(crate_name, commands)| {
+                let commands = commands.iter().map(CommandInfo::to_json).collect();
+                (crate_name.to_string(), serde_json::Value::Array(commands))
+            })
+            .collect();
+        serde_json::json!({ "crates": crates })
+    }
+
+    /// Returns the warnings raised while scanning.
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.warnings.iter().map(String::as_str).chain(
+            self.members
+                .iter()
+                .flat_map(|member| member.warnings.iter().map(String::as_str)),
+        )
+    }
+}
+
+/// Scans a workspace member, `package_name` being the name of the workspace package.
+pub(crate) fn scan_member(
+    workspace_root: &Path,
+    member: &str,
+    package_name: &str,
+    options: &TauriHelperOptions,
+    cache: Option<&ScanCache>,
+) -> MemberScan {
+    let manifest_dir = workspace_root.join(member);
+    let crate_name = manifest_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let roots = modules::crate_roots(&manifest_dir);
+
+    // files reachable from the crate roots, then the ones of `src` that are not. The fast scan
+    // doesn't parse the module tree, it walks the directories of the crate roots as well and
+    // every file's module but the crate roots' is guessed from its location
+    let (module_map, reexports, src_dirs) = if options.fast_scan {
+        let module_map = roots
+            .iter()
+            .map(|root| (modules::normalize(root), ModuleInfo::default()))
+            .collect();
+        let src_dirs = modules::source_dirs(&manifest_dir, &roots);
+        (module_map, Default::default(), src_dirs)
+    } else {
+        let (module_map, reexports) = modules::module_map(&roots);
+        (module_map, reexports, vec![manifest_dir.join("src")])
+    };
+    let mut rs_files: Vec<(PathBuf, ModuleInfo)> = module_map.into_iter().collect();
+    let reachable: HashSet<PathBuf> = rs_files.iter().map(|(p, _)| p.clone()).collect();
+    if let Some(max_depth) = options.max_depth {
+        // files outside of the walked directories, included through `#[path]`, have no depth
+        rs_files.retain(|(path, _)| {
+            src_dirs
+                .iter()
+                .find_map(|dir| path.strip_prefix(modules::normalize(dir)).ok())
+                .is_none_or(|relative| relative.components().count() <= max_depth)
+        });
+    }
+    for src_dir in &src_dirs {
+        let mut walk = WalkDir::new(src_dir);
+        if let Some(max_depth) = options.max_depth {
+            walk = walk.max_depth(max_depth);
+        }
+        rs_files.extend(
+            walk.into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("rs"))
+                .filter(|e| !reachable.contains(&modules::normalize(e.path())))
+                .map(|e| {
+                    let module = modules::module_from_location(src_dir, e.path());
+                    (e.into_path(), module)
+                }),
+        );
+    }
+
+    // the files are scanned in parallel, sorting them keeps the command file deterministic
+    rs_files.sort_by(|a, b| a.0.cmp(&b.0));
+    let file_scans: Vec<FileScan> = rs_files
+        .par_iter()
+        .map(|(path, module)| scan_file(path, module, options, cache))
+        .collect();
+
+    let mut scanned = Vec::new();
+    let mut warnings = Vec::new();
+    let mut unannotated = Vec::new();
+    let mut includes = Vec::new();
+    let mut structs = Vec::new();
+    for file_scan in file_scans {
+        scanned.extend(file_scan.commands);
+        warnings.extend(file_scan.warnings);
+        unannotated.extend(file_scan.unannotated);
+        includes.extend(file_scan.includes);
+        structs.extend(file_scan.structs);
+    }
+
+    // generated files can include generated files in turn
+    let mut files_scanned = rs_files.len();
+    while let Some(include) = includes.pop() {
+        if registry::OUT_DIR_FILES.contains(&include.file.trim_start_matches('/')) {
+            continue;
+        }
+        let Some(path) = out_dir_file(workspace_root, &manifest_dir, &include.file) else {
+            warnings.push(format!(
+                "`{}` included from {} was not found in the `OUT_DIR` of `{}`, its commands are skipped. Build the crate once to generate it",
+                include.file,
+                include.from.display(),
+                crate_name
+            ));
+            continue;
+        };
+        let file_scan = scan_file(&path, &include.module, options, cache);
+        files_scanned += 1;
+        scanned.extend(file_scan.commands);
+        warnings.extend(file_scan.warnings);
+        unannotated.extend(file_scan.unannotated);
+        includes.extend(file_scan.includes);
+        structs.extend(file_scan.structs);
+    }
+
+    let metadata_only = options
+        .metadata_only_crates
+        .iter()
+        .any(|name| name.replace('-', "_") == crate_name.replace('-', "_"));
+    let commands: Vec<CommandInfo> = scanned
+        .iter()
+        .map(|func| {
+            // functions that can't be reached from outside of the crate are assumed to be
+            // re-exported from its root
+            let public_path = func
+                .public
+                .then(|| reexports.public_path(&func.module, &func.name))
+                .flatten();
+            let unreachable = public_path.is_none();
+            let path = public_path.map_or_else(|| func.name.clone(), |path| path.join("::"));
+            let full_name = if crate_name.replace('-', "_") == "src_tauri" {
+                format!("{}::{}", package_name, path)
+            } else {
+                format!("{}::{}", crate_name.replace('-', "_"), path)
+            };
+            let mut command = match options.reexport_paths.get(&crate_name) {
+                Some(reexport) => {
+                    let mut command = func.to_command_info(reexport_path(reexport, &func.name));
+                    // the re-exporting crate reaches the command through the re-export module
+                    command.module = reexport_module(reexport);
+                    command
+                }
+                None => {
+                    let mut command = func.to_command_info(full_name);
+                    command.unreachable = unreachable;
+                    command
+                }
+            };
+            apply_rename_map(&mut command, &options.rename_map);
+            if let Some(prefix) = &options.name_prefix
+                && !metadata_only
+            {
+                apply_name_prefix(&mut command, prefix);
+            }
+            command.metadata_only = metadata_only;
+            // the fast scan doesn't know the arguments
+            command.no_args = options.tag_no_args
+                && !options.fast_scan
+                && func.signature.frontend_args().next().is_none();
+            command
+        })
+        .collect();
+
+    warnings.extend(feature_warning(&crate_name, &commands));
+
+    MemberScan {
+        members: vec![member.to_string()],
+        crate_name,
+        files_scanned,
+        commands,
+        scanned,
+        warnings,
+        unannotated,
+        structs,
+    }
+}
+
+/// Returns the location of `file` in the `OUT_DIR` of the member located in `manifest_dir`.
+///
+/// Build scripts run per crate, each with its own `OUT_DIR`. Only the one of the crate running the
+/// build script is known, the ones of the other members are looked up next to it in the build
+/// directory of the profile, `target/<profile>/build/<package>-<hash>/out`, the most recently
+/// generated file winning.
+fn out_dir_file(workspace_root: &Path, manifest_dir: &Path, file: &str) -> Option<PathBuf> {
+    let file = file.trim_start_matches('/');
+    let out_dir = env::var_os("OUT_DIR").map(PathBuf::from);
+    let building = env::var_os("CARGO_MANIFEST_DIR")
+        .is_some_and(|dir| modules::normalize(Path::new(&dir)) == modules::normalize(manifest_dir));
+    if let Some(out_dir) = &out_dir
+        && building
+    {
+        let path = out_dir.join(file);
+        return path.is_file().then_some(path);
+    }
+
+    // outside of a build script, e.g. in `cargo tauri-helper`, the debug profile is assumed
+    let build_dir = out_dir
+        .as_deref()
+        .and_then(|out_dir| Some(out_dir.parent()?.parent()?.to_path_buf()))
+        .unwrap_or_else(|| workspace_root.join("target").join("debug").join("build"));
+    let prefix = format!("{}-", get_member_manifest(manifest_dir).package?.name);
+    fs::read_dir(build_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        // `<package>-<hash>`, not the directory of another package starting with the same name
+        .filter(|entry| {
+            entry.file_name().to_str().is_some_and(|name| {
+                name.strip_prefix(&prefix)
+                    .is_some_and(|hash| !hash.contains('-'))
+            })
+        })
+        .map(|entry| entry.path().join("out").join(file))
+        .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Merges the scans of the members sharing a command file, the members being scanned in parallel.
+///
+/// The command file of a member is named after its directory, members in directories of the same
+/// name would otherwise overwrite each other's file. The scans are returned sorted by crate, with
+/// a warning for each merged crate.
+pub(crate) fn group_by_crate(scans: Vec<MemberScan>) -> (Vec<MemberScan>, Vec<String>) {
+    let mut crates: BTreeMap<String, MemberScan> = BTreeMap::new();
+    for scan in scans {
+        match crates.get_mut(&scan.crate_name) {
+            Some(merged) => {
+                merged.members.extend(scan.members);
+                merged.files_scanned += scan.files_scanned;
+                merged.commands.extend(scan.commands);
+                merged.scanned.extend(scan.scanned);
+                merged.warnings.extend(scan.warnings);
+                merged.unannotated.extend(scan.unannotated);
+                merged.structs.extend(scan.structs);
+            }
+            None => {
+                crates.insert(scan.crate_name.clone(), scan);
+            }
+        }
+    }
+
+    let warnings = crates
+        .values()
+        .filter(|scan| scan.members.len() > 1)
+        .map(|scan| {
+            format!(
+                "members {} share the command file `{}.txt`, their commands are merged into it",
+                scan.members.join(", "),
+                scan.crate_name
+            )
+        })
+        .collect();
+    (crates.into_values().collect(), warnings)
+}
+
+/// Applies the duplicate policy to the commands registered under the same name, `members` being
+/// sorted by crate. Metadata-only commands are never registered and can't clash.
+///
+/// # Panics
+///
+/// Panics if there are duplicates and the policy is [`DuplicatePolicy::Error`].
+pub(crate) fn apply_duplicate_policy(
+    members: &mut [MemberScan],
+    policy: DuplicatePolicy,
+) -> Vec<String> {
+    // the commands registered under each name, as (member, command) indices in scan order
+    let mut owners: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+    for (m, member) in members.iter().enumerate() {
+        for (c, command) in member.commands.iter().enumerate() {
+            if command.metadata_only {
+                continue;
+            }
+            // a name listed twice by a command, e.g. in a hand-written command file, is one owner
+            let registered: BTreeSet<&str> = std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(String::as_str))
+                .collect();
+            for name in registered {
+                owners.entry(name).or_default().push((m, c));
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    let mut dropped = BTreeSet::new();
+    for (name, owners) in owners.iter().filter(|(_, owners)| owners.len() > 1) {
+        let path = |&(m, c): &(usize, usize)| members[m].commands[c].path.as_str();
+        let paths = owners.iter().map(path).collect::<Vec<_>>().join(", ");
+        let kept = match policy {
+            DuplicatePolicy::Error => {
+                errors.push(format!("`{}` by {}", name, paths));
+                continue;
+            }
+            DuplicatePolicy::Warn => {
+                warnings.push(format!(
+                    "`{}` is registered by several commands ({}), rename them or set `on_duplicate`",
+                    name, paths
+                ));
+                continue;
+            }
+            DuplicatePolicy::FirstWins => owners[0],
+            DuplicatePolicy::LastWins => owners[owners.len() - 1],
+        };
+        dropped.extend(owners.iter().copied().filter(|owner| *owner != kept));
+        warnings.push(format!(
+            "`{}` is registered by several commands ({}), only `{}` is kept",
+            name,
+            paths,
+            path(&kept)
+        ));
+    }
+
+    if !errors.is_empty() {
+        panic!(
+            "Several commands are registered under the same name:\n  {}\nRename them or set `on_duplicate`",
+            errors.join("\n  ")
+        );
+    }
+
+    for (m, member) in members.iter_mut().enumerate() {
+        let mut c = 0;
+        member.commands.retain(|_| {
+            c += 1;
+            !dropped.contains(&(m, c - 1))
+        });
+        let mut c = 0;
+        member.scanned.retain(|_| {
+            c += 1;
+            !dropped.contains(&(m, c - 1))
+        });
+    }
+    warnings
+}
+
+/// Returns a warning for every name a command is registered under that doesn't follow the
+/// convention. Metadata-only commands are never registered and aren't checked.
+pub(crate) fn naming_warnings(members: &[MemberScan], convention: NamingConvention) -> Vec<String> {
+    members
+        .iter()
+        .flat_map(|member| &member.commands)
+        .filter(|command| !command.metadata_only)
+        .flat_map(|command| {
+            std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(|alias| alias.as_str()))
+                .filter(move |name| !convention.matches(name))
+                .map(move |name| {
+                    format!(
+                        "command `{}` is registered as `{}`, which doesn't follow the {:?} naming convention",
+                        command.path, name, convention
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Returns the path of a command re-exported from the module at `reexport`, see
+/// `TauriHelperOptions::reexport_paths`.
+fn reexport_path(reexport: &str, name: &str) -> String {
+    let path = format!("{}::{}", reexport.trim_end_matches("::"), name);
+    if !is_command_path(&path) {
+        panic!(
+            "Invalid re-export path `{}`, expected the path of a module such as `my_app::plugin_commands`",
+            reexport
+        );
+    }
+    path
+}
+
+/// Returns the path of the re-export module of `TauriHelperOptions::reexport_paths` from the root of
+/// its crate, `None` for the crate root.
+fn reexport_module(reexport: &str) -> Option<String> {
+    reexport
+        .trim_end_matches("::")
+        .split_once("::")
+        .map(|(_, module)| module.to_string())
+}
+
+/// Applies the entry of the rename map matching the command, if any.
+fn apply_rename_map(command: &mut CommandInfo, rename_map: &HashMap<String, String>) {
+    let Some(renamed) = rename_map.get(&command.path) else {
+        return;
+    };
+
+    if let Some(name) = &command.name
+        && name != renamed
+    {
+        panic!(
+            "Command `{}` is renamed to `{}` by its attribute but to `{}` by the rename map",
+            command.path, name, renamed
+        );
+    }
+
+    if syn::parse_str::<syn::Ident>(renamed).is_err() {
+        panic!(
+            "Invalid name `{}` for `{}` in the rename map, it must be a valid Rust identifier",
+            renamed, command.path
+        );
+    }
+
+    command.name = Some(renamed.clone());
+}
+
+/// Prefixes the name and the aliases of the command.
+fn apply_name_prefix(command: &mut CommandInfo, prefix: &str) {
+    let prefixed = format!("{}{}", prefix, command.exposed_name());
+    if syn::parse_str::<syn::Ident>(&prefixed).is_err() {
+        panic!(
+            "Invalid `name_prefix` `{}`, `{}` is not a valid Rust identifier",
+            prefix, prefixed
+        );
+    }
+    command.name = Some(prefixed);
+    for alias in &mut command.aliases {
+        alias.insert_str(0, prefix);
+    }
+}
+
+/// Prints the warnings of the scan as `cargo:warning` lines.
+pub(crate) fn emit_warnings(scan: &WorkspaceScan) {
+    for warning in scan.warnings() {
+        println!("cargo:warning=tauri-helper: {}", warning);
+    }
+}
+
+/// Prints a summary of the scan as `cargo:warning` lines.
+pub(crate) fn report_summary(scans: &[MemberScan], elapsed: Duration, files_cached: usize) {
+    for line in summary(scans, elapsed, files_cached) {
+        println!("cargo:warning=tauri-helper: {}", line);
+    }
+}
+
+/// Returns the lines of the summary printed by [`report_summary`].
+fn summary(scans: &[MemberScan], elapsed: Duration, files_cached: usize) -> Vec<String> {
+    let files: usize = scans.iter().map(|scan| scan.files_scanned).sum();
+    let commands: usize = scans.iter().map(|scan| scan.commands.len()).sum();
+
+    let mut lines = vec![format!(
+        "scanned {} files in {} crates, found {} commands in {:.2?}",
+        files,
+        scans.len(),
+        commands,
+        elapsed
+    )];
+    if files_cached > 0 {
+        lines.push(format!(
+            "  {} files unchanged since the previous scan were read from the cache",
+            files_cached
+        ));
+    }
+    for scan in scans {
+        lines.push(format!(
+            "  {}: {} commands ({} files)",
+            scan.crate_name,
+            scan.commands.len(),
+            scan.files_scanned
+        ));
+    }
+    lines
+}
+
+/// Returns `true` if the last segment of the attribute path is `name`, so that both
+/// `#[auto_collect_command]` and `#[tauri_helper::auto_collect_command]` match.
+fn attr_name_is(attr: &syn::Attribute, name: &str) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Returns `true` if the attribute is `#[command]` or `#[tauri::command]`.
+fn is_tauri_command(attr: &syn::Attribute) -> bool {
+    let p = attr.path();
+    p.is_ident("command")
+        || (p.segments.len() == 2
+            && p.segments[0].ident == "tauri"
+            && p.segments[1].ident == "command")
+}
+
+/// Returns `true` if the attribute is a `#[cfg(...)]` that only holds in test builds.
+pub(crate) fn is_cfg_test(attr: &syn::Attribute) -> bool {
+    fn requires_test(meta: &syn::Meta) -> bool {
+        match meta {
+            syn::Meta::Path(path) => path.is_ident("test"),
+            syn::Meta::List(list) if list.path.is_ident("all") => list
+                .parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                .is_ok_and(|metas| metas.iter().any(requires_test)),
+            _ => false,
+        }
+    }
+
+    attr.path().is_ident("cfg")
+        && attr
+            .parse_args::<syn::Meta>()
+            .is_ok_and(|meta| requires_test(&meta))
+}
+
+/// Returns the attributes along with the ones `#[cfg_attr(predicate, ...)]` adds, each with the
+/// predicates it is conditional on, nested `cfg_attr` included.
+fn expand_cfg_attr(attrs: &[syn::Attribute]) -> Vec<(syn::Attribute, Vec<String>)> {
+    fn expand(
+        attr: syn::Attribute,
+        cfg: Vec<String>,
+        out: &mut Vec<(syn::Attribute, Vec<String>)>,
+    ) {
+        let parsed = attr.path().is_ident("cfg_attr").then(|| {
+            attr.parse_args_with(|input: syn::parse::ParseStream| {
+                let predicate: syn::Meta = input.parse()?;
+                input.parse::<Token![,]>()?;
+                let metas = Punctuated::<syn::Meta, Token![,]>::parse_terminated(input)?;
+                Ok((predicate, metas))
+            })
+        });
+        match parsed {
+            Some(Ok((predicate, metas))) => {
+                let mut cfg = cfg;
+                cfg.push(predicate.to_token_stream().to_string());
+                for meta in metas {
+                    let attr = syn::Attribute {
+                        meta,
+                        ..attr.clone()
+                    };
+                    expand(attr, cfg.clone(), out);
+                }
+            }
+            _ => out.push((attr, cfg)),
+        }
+    }
+
+    let mut out = Vec::new();
+    for attr in attrs {
+        expand(attr.clone(), Vec::new(), &mut out);
+    }
+    out
+}
+
+/// Returns the predicates of the `#[cfg(...)]` attributes.
+pub(crate) fn cfg_predicates(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .map(|list| list.tokens.to_string())
+        .collect()
+}
+
+/// Scans a single source file for commands, files that can't be read or parsed have none.
+///
+/// `module` is the module the file is loaded as, commands found in inline modules are
+/// attributed to the corresponding submodule.
+pub(crate) fn scan_file(
+    path: &Path,
+    module: &ModuleInfo,
+    options: &TauriHelperOptions,
+    cache: Option<&ScanCache>,
+) -> FileScan {
+    let mut scan = FileScan::default();
+    if module.test_only && !options.include_test_commands {
+        return scan;
+    }
+
+    if let Some(max_file_size) = options.max_file_size
+        && let Ok(metadata) = fs::metadata(path)
+        && metadata.len() > max_file_size
+    {
+        scan.warnings.push(format!(
+            "skipped {} ({} bytes), it is larger than `max_file_size` ({} bytes)",
+            path.display(),
+            metadata.len(),
+            max_file_size
+        ));
+        return scan;
+    }
+
+    let Ok(content) = fs::read_to_string(path) else {
+        return scan;
+    };
+    match cache {
+        Some(cache) => cache.get_or_scan(path, &content, module, options, || {
+            scan_content(path, &content, module, options)
+        }),
+        None => scan_content(path, &content, module, options),
+    }
+}
+
+/// Scans the content of a file, see [`scan_file`].
+fn scan_content(
+    path: &Path,
+    content: &str,
+    module: &ModuleInfo,
+    options: &TauriHelperOptions,
+) -> FileScan {
+    let mut scan = FileScan::default();
+    let has_marker = std::iter::once(options.marker_attribute.as_str())
+        .chain(options.marker_aliases.iter().map(String::as_str))
+        .chain(["auto_collect_commands"])
+        .any(|marker| content.contains(marker));
+    // the structs commands return can be defined anywhere
+    let wants_structs = options.return_schemas.is_some() && content.contains("struct");
+    if !content.contains("tauri::command")
+        && !has_marker
+        && !content.contains("OUT_DIR")
+        && !wants_structs
+    {
+        return scan;
+    }
+    if options.fast_scan {
+        return fast_scan::scan_content(content, path, module, options);
+    }
+
+    let ast = match parse_file(content) {
+        Ok(ast) => ast,
+        Err(err) => {
+            // unparsable files are usually using syntax newer than the `syn` version in use
+            scan.warnings.push(format!(
+                "could not parse {} ({}), its commands are skipped. If it uses recent syntax, updating `syn` may fix it",
+                path.display(),
+                err
+            ));
+            return scan;
+        }
+    };
+    let mut scanner = FileScanner {
+        path,
+        options,
+        module: module.path.clone(),
+        cfg: module.cfg.clone(),
+        found: Vec::new(),
+        warnings: Vec::new(),
+        unannotated: Vec::new(),
+        includes: Vec::new(),
+        structs: Vec::new(),
+    };
+    scanner.scan_items(&ast.items, &[]);
+
+    scan.commands = scanner.found;
+    scan.warnings.extend(scanner.warnings);
+    scan.unannotated = scanner.unannotated;
+    scan.includes = scanner.includes;
+    scan.structs = scanner.structs;
+    scan
+}
+
+/// State of the scan of a single file.
+struct FileScanner<'a> {
+    path: &'a Path,
+    options: &'a TauriHelperOptions,
+    /// Path of the module currently being scanned.
+    module: Vec<String>,
+    /// `#[cfg(...)]` predicates of the module currently being scanned.
+    cfg: Vec<String>,
+    found: Vec<ScannedCommand>,
+    warnings: Vec<String>,
+    unannotated: Vec<String>,
+    includes: Vec<OutDirInclude>,
+    structs: Vec<StructDef>,
+}
+
+impl FileScanner<'_> {
+    /// Collects the commands of a list of items, descending into inline modules.
+    ///
+    /// Only parsed items are looked at, never the content of attributes: commands written in doc
+    /// comment examples live in `#[doc]` attributes and must never be collected, even though the
+    /// text prefilter of [`scan_file`] matches them.
+    ///
+    /// `listed` holds the functions listed by an `#[auto_collect_commands(...)]` attribute on the
+    /// module, they are collected without being annotated.
+    fn scan_items(&mut self, items: &[syn::Item], listed: &[String]) {
+        let first = self.found.len();
+        for item in items {
+            match item {
+                syn::Item::Fn(func) => {
+                    let is_listed = listed.contains(&func.sig.ident.to_string());
+                    self.scan_fn(func, is_listed)
+                }
+                syn::Item::Mod(module) => {
+                    // `#[cfg(test)] mod tests { ... }` never exists in the builds the handler is part of
+                    if !self.options.include_test_commands && module.attrs.iter().any(is_cfg_test) {
+                        continue;
+                    }
+                    if let Some((_, items)) = &module.content {
+                        let listed = self.listed_commands(module);
+                        let cfg_len = self.cfg.len();
+                        self.cfg.extend(cfg_predicates(&module.attrs));
+                        self.module.push(module.ident.to_string());
+                        self.scan_items(items, &listed);
+                        self.module.pop();
+                        self.cfg.truncate(cfg_len);
+                    }
+                }
+                syn::Item::Impl(item) if item.trait_.is_some() => self.warn_trait_commands(item),
+                syn::Item::Struct(item) if self.options.return_schemas.is_some() => {
+                    self.structs.extend(StructDef::from_syn(item));
+                }
+                syn::Item::Macro(item) if item.mac.path.is_ident("include") => {
+                    if let Some(file) = out_dir_include(&item.mac) {
+                        let mut cfg = self.cfg.clone();
+                        cfg.extend(cfg_predicates(&item.attrs));
+                        self.includes.push(OutDirInclude {
+                            file,
+                            module: ModuleInfo {
+                                path: self.module.clone(),
+                                test_only: false,
+                                cfg,
+                            },
+                            from: self.path.to_path_buf(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // commands found in this module, not in its inline submodules
+        let shadowing = shadowing_names(items);
+        for command in &self.found[first..] {
+            let name = command.name.trim_start_matches("r#");
+            if command.module == self.module && shadowing.contains(name) {
+                let mut path = self.module.clone();
+                path.push(command.name.clone());
+                self.warnings.push(format!(
+                    "command `{}` in {} shares its name with another item of its module, the generated path may not resolve to the function",
+                    path.join("::"),
+                    self.path.display()
+                ));
+            }
+        }
+    }
+
+    /// Warns about the methods of a trait impl marked as commands. Tauri can only register free
+    /// functions, such methods would otherwise be silently left out.
+    fn warn_trait_commands(&mut self, item: &syn::ItemImpl) {
+        for impl_item in &item.items {
+            let syn::ImplItem::Fn(method) = impl_item else {
+                continue;
+            };
+            let is_command = expand_cfg_attr(&method.attrs).iter().any(|(attr, _)| {
+                is_tauri_command(attr)
+                    || std::iter::once(&self.options.marker_attribute)
+                        .chain(&self.options.marker_aliases)
+                        .any(|name| attr_name_is(attr, name))
+            });
+            if is_command {
+                let (_, trait_path, _) = item.trait_.as_ref().unwrap();
+                self.warnings.push(format!(
+                    "command `{}` at {}:{} is a method of an `impl {} for {}` block, trait methods can't be registered as commands and it was skipped. Move it to a free function, e.g. one calling the method",
+                    method.sig.ident,
+                    self.path.display(),
+                    method.sig.ident.span().start().line,
+                    trait_path.to_token_stream().to_string().replace(' ', ""),
+                    item.self_ty.to_token_stream().to_string().replace(' ', ""),
+                ));
+            }
+        }
+    }
+
+    /// Reads the functions listed by an `#[auto_collect_commands(...)]` attribute on a module,
+    /// warning about the ones the module doesn't define.
+    fn listed_commands(&mut self, module: &syn::ItemMod) -> Vec<String> {
+        let Some(attr) = module
+            .attrs
+            .iter()
+            .find(|a| attr_name_is(a, "auto_collect_commands"))
+        else {
+            return Vec::new();
+        };
+
+        let listed: Vec<String> = attr
+            .parse_args_with(Punctuated::<syn::Ident, Token![,]>::parse_terminated)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Invalid `#[auto_collect_commands]` on `{}` in {}: {}",
+                    module.ident,
+                    self.path.display(),
+                    e
+                )
+            })
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        let defined: HashSet<String> = module
+            .content
+            .iter()
+            .flat_map(|(_, items)| items)
+            .filter_map(|item| match item {
+                syn::Item::Fn(func) => Some(func.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        for name in listed.iter().filter(|name| !defined.contains(*name)) {
+            self.warnings.push(format!(
+                "`{}` is listed by `#[auto_collect_commands]` on module `{}` in {} but the module defines no such function",
+                name,
+                module.ident,
+                self.path.display()
+            ));
+        }
+
+        listed
+    }
+
+    /// Collects the function if it is a command. Its attributes are searched, never indexed, so
+    /// that the marker and `#[tauri::command]` can be written in any order.
+    ///
+    /// Attributes added by `#[cfg_attr(predicate, ...)]` count as well, the command is then
+    /// recorded under the predicate so that the handler only registers it when it holds.
+    fn scan_fn(&mut self, func: &syn::ItemFn, is_listed: bool) {
+        if !self.options.include_test_commands && func.attrs.iter().any(is_cfg_test) {
+            return;
+        }
+
+        let attrs = expand_cfg_attr(&func.attrs);
+        let marker = attrs
+            .iter()
+            .find(|(a, _)| attr_name_is(a, &self.options.marker_attribute));
+        let alias_attr = attrs.iter().find(|(a, _)| {
+            self.options
+                .marker_aliases
+                .iter()
+                .any(|name| attr_name_is(a, name))
+        });
+        let alias = alias_attr.is_some();
+        let tauri_command = attrs.iter().find(|(a, _)| is_tauri_command(a));
+
+        // predicates of the `cfg_attr` the function is a command under
+        let mut conditional: Vec<String> = marker
+            .or(alias_attr)
+            .into_iter()
+            .chain(tauri_command)
+            .flat_map(|(_, cfg)| cfg.iter().cloned())
+            .collect();
+        conditional.dedup();
+        let marker = marker.map(|(attr, _)| attr);
+
+        let args = match marker {
+            Some(attr) => CommandArgs::from_attribute(attr)
+                .and_then(|args| args.check_function(&func.sig.ident).map(|_| args))
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Invalid `#[{}]` on `{}` in {}: {}",
+                        self.options.marker_attribute,
+                        func.sig.ident,
+                        self.path.display(),
+                        e
+                    )
+                }),
+            // aliases and module lists only mark the function, they take no arguments
+            None if alias || is_listed => CommandArgs::default(),
+            None if self.options.collect_all && tauri_command.is_some() => CommandArgs::default(),
+            None => {
+                if self.options.require_annotation && tauri_command.is_some() {
+                    let line = func.sig.ident.span().start().line;
+                    self.unannotated.push(format!(
+                        "`{}` at {}:{}",
+                        func.sig.ident,
+                        self.path.display(),
+                        line
+                    ));
+                }
+                return;
+            }
+        };
+
+        if !self.options.include_private && matches!(func.vis, syn::Visibility::Inherited) {
+            // only warn about explicitly marked functions, `collect_all` skips them silently
+            if marker.is_some() || alias || is_listed {
+                self.warnings.push(format!(
+                    "command `{}` in {} is private and was skipped, make it `pub` or enable `include_private`",
+                    func.sig.ident,
+                    self.path.display()
+                ));
+            }
+            return;
+        }
+
+        let generics = non_runtime_generics(&func.sig.generics);
+        if !generics.is_empty() {
+            self.warnings.push(format!(
+                "command `{}` at {}:{} is generic over `{}` and was skipped, Tauri can't register it, call it from a command with concrete types instead",
+                func.sig.ident,
+                self.path.display(),
+                func.sig.ident.span().start().line,
+                generics.join("`, `")
+            ));
+            return;
+        }
+
+        let mut cfg = self.cfg.clone();
+        cfg.extend(cfg_predicates(&func.attrs));
+        cfg.extend(conditional);
+
+        self.found.push(ScannedCommand {
+            name: func.sig.ident.to_string(),
+            module: self.module.clone(),
+            cfg,
+            args,
+            signature: CommandSignature::from_syn(&func.sig),
+            signature_hash: lockfile::signature_hash(&func.sig),
+            public: matches!(func.vis, syn::Visibility::Public(_)),
+        });
+    }
+}
+
+/// Returns the path relative to `OUT_DIR` of an `include!(concat!(env!("OUT_DIR"), "..."))`, `None`
+/// for any other include.
+fn out_dir_include(include: &syn::Macro) -> Option<String> {
+    let syn::Expr::Macro(concat) = include.parse_body::<syn::Expr>().ok()? else {
+        return None;
+    };
+    if !concat.mac.path.is_ident("concat") {
+        return None;
+    }
+    let mut parts = concat
+        .mac
+        .parse_body_with(Punctuated::<syn::Expr, Token![,]>::parse_terminated)
+        .ok()?
+        .into_iter();
+    let syn::Expr::Macro(env) = parts.next()? else {
+        return None;
+    };
+    if !env.mac.path.is_ident("env")
+        || env.mac.parse_body::<syn::LitStr>().ok()?.value() != "OUT_DIR"
+    {
+        return None;
+    }
+    parts
+        .map(|part| match part {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the names of the items other than functions defined or imported in a module.
+fn shadowing_names(items: &[syn::Item]) -> HashSet<String> {
+    fn use_names(tree: &syn::UseTree, names: &mut HashSet<String>) {
+        match tree {
+            syn::UseTree::Path(path) => use_names(&path.tree, names),
+            syn::UseTree::Name(name) => {
+                names.insert(name.ident.to_string());
+            }
+            syn::UseTree::Rename(rename) => {
+                names.insert(rename.rename.to_string());
+            }
+            syn::UseTree::Group(group) => group.items.iter().for_each(|t| use_names(t, names)),
+            syn::UseTree::Glob(_) => {}
+        }
+    }
+
+    let mut names = HashSet::new();
+    for item in items {
+        let ident = match item {
+            syn::Item::Struct(item) => &item.ident,
+            syn::Item::Enum(item) => &item.ident,
+            syn::Item::Union(item) => &item.ident,
+            syn::Item::Type(item) => &item.ident,
+            syn::Item::Const(item) => &item.ident,
+            syn::Item::Static(item) => &item.ident,
+            syn::Item::Trait(item) => &item.ident,
+            syn::Item::Mod(item) => &item.ident,
+            syn::Item::Use(item) => {
+                use_names(&item.tree, &mut names);
+                continue;
+            }
+            _ => continue,
+        };
+        names.insert(ident.to_string());
+    }
+
+    names
+        .into_iter()
+        .map(|name| name.trim_start_matches("r#").to_string())
+        .collect()
+}
+
+/// Returns a warning listing the features of a crate that guard some of its commands, skipping
+/// the features enabled for the crate running the build script.
+///
+/// The handler evaluates the `cfg` of the commands in the crate invoking it, which is usually the
+/// one running the build script, so its features are the ones that matter.
+pub(crate) fn feature_warning(crate_name: &str, commands: &[CommandInfo]) -> Option<String> {
+    let mut features = BTreeMap::<String, Vec<&str>>::new();
+    for command in commands {
+        for feature in command.features() {
+            let enabled = env::var_os(format!(
+                "CARGO_FEATURE_{}",
+                feature.to_uppercase().replace('-', "_")
+            ))
+            .is_some();
+            if !enabled {
+                features
+                    .entry(feature)
+                    .or_default()
+                    .push(command.exposed_name());
+            }
+        }
+    }
+
+    if features.is_empty() {
+        return None;
+    }
+
+    let list = features
+        .iter()
+        .map(|(feature, commands)| format!("`{}` ({})", feature, commands.join(", ")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "some commands of `{}` are gated behind features, enable {} on the crate generating the handler (forwarding them to `{}` if needed) for them to be registered",
+        crate_name, list, crate_name
+    ))
+}
+
+/// Returns the type and const parameters of a function that keep it from being registered.
+///
+/// Lifetimes are fine, and so are type parameters bounded by `Runtime`, e.g.
+/// `fn open<R: Runtime>(app: AppHandle<R>)`, which Tauri infers when generating the handler.
+fn non_runtime_generics(generics: &syn::Generics) -> Vec<String> {
+    let is_runtime = |bounds: &syn::punctuated::Punctuated<syn::TypeParamBound, syn::Token![+]>| {
+        bounds.iter().any(|bound| {
+            matches!(bound, syn::TypeParamBound::Trait(bound)
+                if bound.path.segments.last().is_some_and(|s| s.ident == "Runtime"))
+        })
+    };
+    let bounded_in_where = |ident: &syn::Ident| {
+        generics
+            .where_clause
+            .iter()
+            .flat_map(|w| &w.predicates)
+            .any(|predicate| {
+                matches!(predicate, syn::WherePredicate::Type(predicate)
+                if matches!(&predicate.bounded_ty, syn::Type::Path(ty) if ty.path.is_ident(ident))
+                    && is_runtime(&predicate.bounds))
+            })
+    };
+
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Lifetime(_) => None,
+            syn::GenericParam::Type(ty)
+                if is_runtime(&ty.bounds) || bounded_in_where(&ty.ident) =>
+            {
+                None
+            }
+            syn::GenericParam::Type(ty) => Some(ty.ident.to_string()),
+            syn::GenericParam::Const(param) => Some(param.ident.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+
+    #[test]
+    fn nested_module_commands_get_their_public_path() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            pub mod commands {
+                pub mod users {
+                    #[tauri::command]
+                    #[auto_collect_command]
+                    pub fn greet() {}
+                }
+            }
+            "#,
+        );
+
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::commands::users::greet"]
+        );
+    }
+
+    #[test]
+    fn commands_of_private_modules_go_through_their_root_reexport() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "mod commands;\npub use commands::{__cmd__greet, greet};\n",
+            )
+            .file(
+                "cmds/src/commands/mod.rs",
+                "mod users;\npub use users::{__cmd__greet, greet};\n",
+            )
+            .file(
+                "cmds/src/commands/users.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let command = &scan.members[0].commands[0];
+        assert_eq!(command.path, "cmds::greet");
+        assert!(!command.unreachable);
+        // `users` is private to `commands`, the crate root can't go through it
+        assert_eq!(command.path_in_crate(), "greet");
+    }
+
+    #[test]
+    fn unreachable_commands_go_through_their_module_in_their_crate() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file("cmds/src/lib.rs", "mod commands;\n")
+            .file(
+                "cmds/src/commands.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let command = &scan.members[0].commands[0];
+        assert_eq!(command.path, "cmds::greet");
+        assert!(command.unreachable);
+        assert_eq!(command.path_in_crate(), "commands::greet");
+    }
+
+    #[test]
+    fn glob_reexports_of_internal_modules_are_registered_from_the_root() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "mod internal;\npub use internal::commands::*;\n",
+            )
+            .file("cmds/src/internal/mod.rs", "pub mod commands;\n")
+            .file(
+                "cmds/src/internal/commands.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            );
+
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::greet"]
+        );
+    }
+
+    fn reexported_plugin_command(reexport: &str) -> CommandInfo {
+        let fixture = Fixture::workspace(&["plugin"]).file(
+            "plugin/src/lib.rs",
+            r#"
+            pub mod commands {
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn greet() {}
+            }
+            "#,
+        );
+        let options = TauriHelperOptions::builder()
+            .reexport_path("plugin", reexport)
+            .build();
+
+        let scan = fixture.scan(&options);
+        let commands = scan.members[0].commands.clone();
+        // the app registers the commands of the plugin
+        let mut commands = tauri_helper_core::command::normalize_commands("app", "app", commands);
+        commands.pop_first().unwrap()
+    }
+
+    #[test]
+    fn reexported_plugin_commands_are_referred_to_through_the_reexport_module() {
+        let command = reexported_plugin_command("app::plugin_commands");
+        assert_eq!(command.path, "crate::plugin_commands::greet");
+        assert_eq!(command.module.as_deref(), Some("plugin_commands"));
+    }
+
+    #[test]
+    fn plugin_commands_reexported_from_the_app_root_are_referred_to_from_the_root() {
+        let command = reexported_plugin_command("app");
+        assert_eq!(command.path, "crate::greet");
+        assert_eq!(command.module, None);
+    }
+
+    fn greet_fixture() -> Fixture {
+        Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(rename = "bye")]
+            pub fn farewell() {}
+            "#,
+        )
+    }
+
+    #[test]
+    fn rename_map_renames_the_commands_of_its_paths() {
+        let options = TauriHelperOptions::builder()
+            .rename("cmds::greet", "hello")
+            .build();
+
+        let scan = greet_fixture().scan(&options);
+        let names: Vec<&str> = scan.members[0]
+            .commands
+            .iter()
+            .map(CommandInfo::exposed_name)
+            .collect();
+        assert_eq!(names, ["hello", "bye"]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Command `cmds::farewell` is renamed to `bye` by its attribute but to `goodbye` by the rename map"
+    )]
+    fn rename_map_conflicting_with_the_attribute_fails() {
+        let options = TauriHelperOptions::builder()
+            .rename("cmds::farewell", "goodbye")
+            .build();
+        greet_fixture().scan(&options);
+    }
+
+    fn test_only_fixture() -> Fixture {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn greet() {}
+
+                #[cfg(test)]
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn test_helper() {}
+
+                #[cfg(test)]
+                mod tests {
+                    #[tauri::command]
+                    #[auto_collect_command]
+                    pub fn inline_test_command() {}
+                }
+
+                #[cfg(test)]
+                mod more_tests;
+                "#,
+            )
+            .file(
+                "cmds/src/more_tests.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn file_test_command() {}\n",
+            )
+    }
+
+    #[test]
+    fn test_only_commands_are_skipped() {
+        assert_eq!(
+            test_only_fixture().command_paths(&TauriHelperOptions::default()),
+            ["cmds::greet"]
+        );
+    }
+
+    #[test]
+    fn test_only_commands_are_collected_on_request() {
+        let options = TauriHelperOptions::builder()
+            .include_test_commands(true)
+            .build();
+        let mut paths = test_only_fixture().command_paths(&options);
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "cmds::file_test_command",
+                "cmds::greet",
+                "cmds::inline_test_command",
+                "cmds::test_helper"
+            ]
+        );
+    }
+
+    #[test]
+    fn verbose_summary_counts_files_and_commands_per_crate() {
+        let fixture = Fixture::workspace(&["cmds", "empty"])
+            .file(
+                "cmds/src/lib.rs",
+                "mod users;\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/users.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            )
+            .file("empty/src/lib.rs", "pub fn helper() {}\n");
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let lines = summary(&scan.members, scan.elapsed, scan.files_cached);
+        assert!(
+            lines[0].starts_with("scanned 3 files in 2 crates, found 2 commands in "),
+            "{}",
+            lines[0]
+        );
+        assert_eq!(
+            lines[1..],
+            [
+                "  cmds: 2 commands (2 files)",
+                "  empty: 0 commands (1 files)"
+            ]
+        );
+    }
+
+    fn relocated_fixture() -> Fixture {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/Cargo.toml",
+                "[package]\nname = \"cmds\"\nversion = \"0.1.0\"\n\n[lib]\npath = \"code/lib.rs\"\n",
+            )
+            .file(
+                "cmds/code/lib.rs",
+                "#[path = \"../handlers/user_commands.rs\"]\npub mod users;\npub mod settings;\n",
+            )
+            .file(
+                "cmds/code/settings.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn set_theme() {}\n",
+            )
+            .file(
+                "cmds/handlers/user_commands.rs",
+                "#[path = \"admin_commands.rs\"]\npub mod admin;\n\n\
+                #[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            )
+            .file(
+                "cmds/handlers/admin_commands.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn ban_user() {}\n",
+            )
+    }
+
+    #[test]
+    fn relocated_lib_root_and_path_modules() {
+        let mut paths = relocated_fixture().command_paths(&TauriHelperOptions::default());
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "cmds::settings::set_theme",
+                "cmds::users::admin::ban_user",
+                "cmds::users::load_user"
+            ]
+        );
+    }
+
+    #[test]
+    fn oversized_files_are_skipped_with_a_warning() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "pub mod generated;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/generated.rs",
+                &format!(
+                    "#[tauri::command]\n#[auto_collect_command]\npub fn huge() {{}}\n{}",
+                    "// padding\n".repeat(100)
+                ),
+            );
+        let options = TauriHelperOptions::builder().max_file_size(500).build();
+
+        let scan = fixture.scan(&options);
+        assert_eq!(fixture.command_paths(&options), ["cmds::greet"]);
+        let warnings: Vec<&str> = scan.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains(
+                "generated.rs (1159 bytes), it is larger than `max_file_size` (500 bytes)"
+            ),
+            "{}",
+            warnings[0]
+        );
+
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::generated::huge", "cmds::greet"]
+        );
+    }
+
+    #[test]
+    fn functions_bearing_any_marker_are_collected() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[legacy_command]
+            pub fn farewell() {}
+
+            #[tauri::command]
+            pub fn unmarked() {}
+            "#,
+        );
+
+        let options = TauriHelperOptions::builder()
+            .marker_alias("legacy_command")
+            .build();
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["cmds::greet", "cmds::farewell"]
+        );
+        assert_eq!(
+            fixture.command_paths(
+                &TauriHelperOptions::builder()
+                    .fast_scan(true)
+                    .marker_alias("legacy_command")
+                    .build()
+            ),
+            ["cmds::greet", "cmds::farewell"]
+        );
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::greet"]
+        );
+    }
+
+    #[test]
+    fn features_gating_commands_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[cfg(feature = "admin")]
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn ban_user() {}
+
+            #[cfg(all(feature = "admin", any(feature = "audit", not(feature = "lite"))))]
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn export_logs() {}
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let features: Vec<Vec<String>> = scan.members[0]
+            .commands
+            .iter()
+            .map(|command| command.features().into_iter().collect())
+            .collect();
+        assert_eq!(features, [vec![], vec!["admin"], vec!["admin", "audit"]]);
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [
+                "some commands of `cmds` are gated behind features, enable `admin` (ban_user, export_logs), `audit` (export_logs) on the crate generating the handler (forwarding them to `cmds` if needed) for them to be registered"
+            ]
+        );
+    }
+
+    #[test]
+    fn recent_syntax_is_parsed() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            unsafe extern "C" {
+                pub safe fn abs(input: i32) -> i32;
+            }
+
+            fn captures<'a>(value: &'a str) -> impl Iterator<Item = char> + use<'a> {
+                value.chars()
+            }
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub async fn first_word(text: Option<String>) -> Option<String> {
+                let split = async |text: String| text.split(' ').next().map(str::to_string);
+                if let Some(text) = text
+                    && !text.is_empty()
+                {
+                    split(text).await
+                } else {
+                    None
+                }
+            }
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(scan.warnings().count(), 0);
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::first_word"]
+        );
+    }
+
+    #[test]
+    fn unparsable_files_are_named_in_a_warning() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "pub mod broken;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/broken.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn lost( {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let warnings: Vec<&str> = scan.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].starts_with("could not parse ")
+                && warnings[0].contains("broken.rs")
+                && warnings[0].ends_with(
+                    "its commands are skipped. If it uses recent syntax, updating `syn` may fix it"
+                ),
+            "{}",
+            warnings[0]
+        );
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::greet"]
+        );
+    }
+
+    #[test]
+    fn commands_shadowed_by_another_item_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            pub mod users {
+                #[allow(non_camel_case_types)]
+                pub struct r#greet;
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn r#greet() {}
+
+                #[tauri::command]
+                #[auto_collect_command]
+                pub fn farewell() {}
+            }
+
+            use users::farewell as load;
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn load() {}
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let warnings: Vec<String> = scan
+            .warnings()
+            .map(|warning| warning.replace(&fixture.root().display().to_string(), "<root>"))
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "command `users::r#greet` in <root>/cmds/src/lib.rs shares its name with another item of its module, the generated path may not resolve to the function",
+                "command `load` in <root>/cmds/src/lib.rs shares its name with another item of its module, the generated path may not resolve to the function",
+            ]
+        );
+    }
+
+    #[test]
+    fn functions_listed_on_their_module_are_collected() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[auto_collect_commands(greet, calculate_sum, missing)]
+            pub mod commands {
+                #[tauri::command]
+                pub fn greet() {}
+
+                #[tauri::command]
+                pub fn calculate_sum(a: i32, b: i32) -> i32 {
+                    a + b
+                }
+
+                #[tauri::command]
+                pub fn not_listed() {}
+            }
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::commands::greet", "cmds::commands::calculate_sum"]
+        );
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "`missing` is listed by `#[auto_collect_commands]` on module `commands` in {} but the module defines no such function",
+                fixture.root().join("cmds/src/lib.rs").display()
+            )]
+        );
+    }
+
+    fn visibility_fixture() -> Fixture {
+        Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub(crate) fn load_user() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            fn reset() {}
+            "#,
+        )
+    }
+
+    #[test]
+    fn private_commands_are_skipped_by_default() {
+        let fixture = visibility_fixture();
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::greet", "cmds::load_user"]
+        );
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "command `reset` in {} is private and was skipped, make it `pub` or enable `include_private`",
+                fixture.root().join("cmds/src/lib.rs").display()
+            )]
+        );
+    }
+
+    #[test]
+    fn private_commands_are_collected_on_request() {
+        let options = TauriHelperOptions::builder().include_private(true).build();
+        assert_eq!(
+            visibility_fixture().command_paths(&options),
+            ["cmds::greet", "cmds::load_user", "cmds::reset"]
+        );
+    }
+
+    #[test]
+    fn commands_taking_only_injected_arguments_are_tagged() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn status(app: tauri::AppHandle, state: State<'_, AppState>) {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn ping() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet(state: State<'_, AppState>, name: String) {}
+            "#,
+        );
+        let no_args = |options: &TauriHelperOptions| -> Vec<bool> {
+            fixture.scan(options).members[0]
+                .commands
+                .iter()
+                .map(|command| command.no_args)
+                .collect()
+        };
+
+        let options = TauriHelperOptions::builder().tag_no_args(true).build();
+        assert_eq!(no_args(&options), [true, true, false]);
+        assert_eq!(
+            no_args(&TauriHelperOptions::default()),
+            [false, false, false]
+        );
+    }
+
+    #[test]
+    fn commands_in_doc_examples_are_not_collected() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r##"
+            //! ```
+            //! #[tauri::command]
+            //! #[auto_collect_command]
+            //! pub fn crate_example() {}
+            //! ```
+
+            /// Greets, registered like this:
+            ///
+            /// ```rust
+            /// #[tauri::command]
+            /// #[auto_collect_command]
+            /// pub fn doc_example() {}
+            /// ```
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[doc = "#[tauri::command]\n#[auto_collect_command]\npub fn attribute_example() {}"]
+            pub struct Documented;
+            "##,
+        );
+
+        for options in [
+            TauriHelperOptions::default(),
+            TauriHelperOptions::builder().collect_all(true).build(),
+            TauriHelperOptions::builder().fast_scan(true).build(),
+        ] {
+            assert_eq!(fixture.command_paths(&options), ["cmds::greet"]);
+        }
+    }
+
+    #[test]
+    fn timeouts_are_recorded_in_the_metadata() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command(timeout_ms = 5000)]
+            pub fn export_data() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        let commands = &scan.members[0].commands;
+        assert_eq!(commands[0].timeout_ms, Some(5000));
+        assert_eq!(commands[0].to_json()["timeout_ms"], 5000);
+        assert_eq!(commands[1].timeout_ms, None);
+        assert!(commands[1].to_json().get("timeout_ms").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "`timeout_ms` must be a positive integer")]
+    fn timeouts_must_be_positive() {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command(timeout_ms = 0)]\npub fn greet() {}\n",
+            )
+            .scan(&TauriHelperOptions::default());
+    }
+
+    #[test]
+    fn commands_below_the_max_depth_are_not_collected() {
+        let fixture = Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "pub mod api;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "cmds/src/api/mod.rs",
+                "pub mod v1;\n\n#[tauri::command]\n#[auto_collect_command]\npub fn status() {}\n",
+            )
+            .file(
+                "cmds/src/api/v1/mod.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn legacy_status() {}\n",
+            );
+
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            [
+                "cmds::api::status",
+                "cmds::api::v1::legacy_status",
+                "cmds::greet"
+            ]
+        );
+        let options = TauriHelperOptions::builder().max_depth(2).build();
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["cmds::api::status", "cmds::greet"]
+        );
+        let options = TauriHelperOptions::builder().max_depth(1).build();
+        assert_eq!(fixture.command_paths(&options), ["cmds::greet"]);
+    }
+
+    #[test]
+    fn generated_commands_come_from_the_out_dir_of_their_member() {
+        let include = "include!(concat!(env!(\"OUT_DIR\"), \"/commands.rs\"));\n";
+        let build_dir = "target/debug/build";
+        let fixture = Fixture::workspace(&["core", "plugin"])
+            .file(
+                "core/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "plugin/src/lib.rs",
+                &format!("pub mod generated {{\n{}}}\n", include),
+            )
+            // the `OUT_DIR` of another package, whose name starts with the member's
+            .file(
+                &format!("{}/plugin-extra-0123abcd/out/commands.rs", build_dir),
+                "#[tauri::command]\n#[auto_collect_command]\npub fn wrong_crate() {}\n",
+            )
+            .file(
+                &format!("{}/plugin-4567cdef/out/commands.rs", build_dir),
+                "#[tauri::command]\n#[auto_collect_command]\npub fn plugin_status() {}\n",
+            );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(scan.warnings().count(), 0);
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["core::greet", "plugin::generated::plugin_status"]
+        );
+
+        // a member that wasn't built yet has no generated file
+        let fixture = fixture.file("core/src/lib.rs", include);
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "`/commands.rs` included from {} was not found in the `OUT_DIR` of `core`, its commands are skipped. Build the crate once to generate it",
+                fixture.root().join("core/src/lib.rs").display()
+            )]
+        );
+    }
+
+    /// Two crates defining `greet`, one of them also loading users.
+    fn duplicates_fixture() -> Fixture {
+        Fixture::workspace(&["settings", "users"])
+            .file(
+                "settings/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+            )
+            .file(
+                "users/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n\n\
+                #[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            )
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Several commands are registered under the same name:\n  `greet` by settings::greet, users::greet"
+    )]
+    fn duplicates_fail_the_build_with_the_error_policy() {
+        let options = TauriHelperOptions::builder()
+            .on_duplicate(DuplicatePolicy::Error)
+            .build();
+        duplicates_fixture().scan(&options);
+    }
+
+    #[test]
+    fn duplicates_are_kept_with_a_warning_by_default() {
+        let fixture = duplicates_fixture();
+        let options = TauriHelperOptions::default();
+        assert_eq!(options.on_duplicate, DuplicatePolicy::Warn);
+
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["settings::greet", "users::greet", "users::load_user"]
+        );
+        assert_eq!(
+            fixture.scan(&options).warnings().collect::<Vec<_>>(),
+            [
+                "`greet` is registered by several commands (settings::greet, users::greet), rename them or set `on_duplicate`"
+            ]
+        );
+    }
+
+    #[test]
+    fn the_first_duplicate_is_kept_with_the_first_wins_policy() {
+        let fixture = duplicates_fixture();
+        let options = TauriHelperOptions::builder()
+            .on_duplicate(DuplicatePolicy::FirstWins)
+            .build();
+
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["settings::greet", "users::load_user"]
+        );
+        assert_eq!(
+            fixture.scan(&options).warnings().collect::<Vec<_>>(),
+            [
+                "`greet` is registered by several commands (settings::greet, users::greet), only `settings::greet` is kept"
+            ]
+        );
+    }
+
+    #[test]
+    fn the_last_duplicate_is_kept_with_the_last_wins_policy() {
+        let fixture = duplicates_fixture();
+        let options = TauriHelperOptions::builder()
+            .on_duplicate(DuplicatePolicy::LastWins)
+            .build();
+
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["users::greet", "users::load_user"]
+        );
+        assert_eq!(
+            fixture.scan(&options).warnings().collect::<Vec<_>>(),
+            [
+                "`greet` is registered by several commands (settings::greet, users::greet), only `users::greet` is kept"
+            ]
+        );
+    }
+
+    #[test]
+    fn attributes_are_found_in_either_order() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command(rename = "hello")]
+            pub fn greet() {}
+
+            #[auto_collect_command(rename = "goodbye")]
+            #[tauri::command]
+            pub fn farewell() {}
+
+            #[auto_collect_command]
+            #[doc = "Loads a user."]
+            #[tauri::command]
+            pub fn load_user() {}
+            "#,
+        );
+
+        for options in [
+            TauriHelperOptions::default(),
+            TauriHelperOptions::builder().collect_all(true).build(),
+        ] {
+            let scan = fixture.scan(&options);
+            let names: Vec<&str> = scan.commands().map(CommandInfo::exposed_name).collect();
+            assert_eq!(names, ["hello", "goodbye", "load_user"]);
+            assert_eq!(scan.warnings().count(), 0);
+            assert!(scan.members[0].unannotated.is_empty());
+        }
+
+        // the fast scan ignores the arguments of the marker
+        let options = TauriHelperOptions::builder().fast_scan(true).build();
+        assert_eq!(
+            fixture.command_paths(&options),
+            ["cmds::greet", "cmds::farewell", "cmds::load_user"]
+        );
+    }
+
+    #[test]
+    fn trait_impl_commands_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "pub trait Greeter {\n    fn greet(&self) -> String;\n}\n\n\
+            pub struct English;\n\n\
+            impl Greeter for English {\n    #[tauri::command]\n    #[auto_collect_command]\n    fn greet(&self) -> String {\n        \"Hello\".to_string()\n    }\n}\n\n\
+            impl English {\n    pub fn name(&self) -> &str {\n        \"en\"\n    }\n}\n\n\
+            #[tauri::command]\n#[auto_collect_command]\npub fn farewell() {}\n",
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::farewell"]
+        );
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "command `greet` at {}:10 is a method of an `impl Greeter for English` block, trait methods can't be registered as commands and it was skipped. Move it to a free function, e.g. one calling the method",
+                fixture.root().join("cmds/src/lib.rs").display()
+            )]
+        );
+    }
+
+    #[test]
+    fn generic_commands_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn parse<T: FromStr, const N: usize>(input: String) {}\n\n\
+            #[tauri::command]\n#[auto_collect_command]\npub fn open<R: Runtime>(app: AppHandle<R>) {}\n\n\
+            #[tauri::command]\n#[auto_collect_command]\npub fn close<R>(app: AppHandle<R>) where R: tauri::Runtime {}\n\n\
+            #[tauri::command]\n#[auto_collect_command]\npub fn greet<'a>(name: &'a str) {}\n",
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            fixture.command_paths(&TauriHelperOptions::default()),
+            ["cmds::open", "cmds::close", "cmds::greet"]
+        );
+        assert_eq!(
+            scan.warnings().collect::<Vec<_>>(),
+            [format!(
+                "command `parse` at {}:3 is generic over `T`, `N` and was skipped, Tauri can't register it, call it from a command with concrete types instead",
+                fixture.root().join("cmds/src/lib.rs").display()
+            )]
+        );
+    }
+
+    #[test]
+    fn relocated_lib_root_with_path_modules_in_inline_modules() {
+        let fixture = relocated_fixture()
+            .file(
+                "cmds/code/lib.rs",
+                "#[path = \"../handlers/user_commands.rs\"]\npub mod users;\npub mod settings;\n\n\
+                #[path = \"../handlers\"]\npub mod api {\n    #[path = \"admin_commands.rs\"]\n    pub mod admin;\n}\n",
+            )
+            .file(
+                "cmds/handlers/user_commands.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn load_user() {}\n",
+            );
+
+        let mut paths = fixture.command_paths(&TauriHelperOptions::default());
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "cmds::api::admin::ban_user",
+                "cmds::settings::set_theme",
+                "cmds::users::load_user"
+            ]
+        );
+    }
+
+    #[test]
+    fn the_fast_scan_walks_the_directory_of_a_relocated_lib_root() {
+        let options = TauriHelperOptions::builder().fast_scan(true).build();
+        // `code/settings.rs` is found next to the crate root, the files loaded through `#[path]`
+        // outside of its directory are not
+        assert_eq!(
+            relocated_fixture().command_paths(&options),
+            ["cmds::set_theme"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`alias = \"greet\"` is the name of the function")]
+    fn aliases_equal_to_the_function_name_are_rejected() {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command(alias = \"greet\")]\npub fn greet() {}\n",
+            )
+            .scan(&TauriHelperOptions::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate `alias`")]
+    fn repeated_aliases_are_rejected() {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command(alias = \"hello\", alias = \"hello\")]\npub fn greet() {}\n",
+            )
+            .scan(&TauriHelperOptions::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "`alias` can't be the `rename` name")]
+    fn aliases_equal_to_the_rename_name_are_rejected() {
+        Fixture::workspace(&["cmds"])
+            .file(
+                "cmds/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command(rename = \"hello\", alias = \"hello\")]\npub fn greet() {}\n",
+            )
+            .scan(&TauriHelperOptions::default());
+    }
+
+    #[test]
+    fn a_name_listed_twice_by_a_command_is_not_a_duplicate() {
+        let mut members = [MemberScan {
+            members: vec!["cmds".to_string()],
+            crate_name: "cmds".to_string(),
+            files_scanned: 1,
+            commands: vec![CommandInfo {
+                aliases: vec![
+                    "greet".to_string(),
+                    "hello".to_string(),
+                    "hello".to_string(),
+                ],
+                ..CommandInfo::new("cmds::greet")
+            }],
+            scanned: Vec::new(),
+            warnings: Vec::new(),
+            unannotated: Vec::new(),
+            structs: Vec::new(),
+        }];
+
+        let warnings = apply_duplicate_policy(&mut members, DuplicatePolicy::Error);
+        assert!(warnings.is_empty());
+        assert_eq!(members[0].commands.len(), 1);
+    }
+}
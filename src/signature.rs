@@ -0,0 +1,279 @@
+use quote::ToTokens;
+use std::fmt;
+use syn::{
+    Fields, FnArg, GenericArgument, ItemStruct, LitStr, Pat, PathArguments, ReturnType, Signature,
+    Token, Type,
+};
+
+/// Types that Tauri injects into a command, they are never sent by the frontend.
+const INJECTED_TYPES: &[&str] = &[
+    "AppHandle",
+    "State",
+    "Window",
+    "WebviewWindow",
+    "Webview",
+    "Request",
+    "CommandScope",
+    "GlobalScope",
+];
+
+/// Owned, simplified representation of a Rust type found in a command signature.
+///
+/// `syn` types can't be sent across the scanning threads, so the parts of the signature needed by
+/// the generators are extracted into this form while the file is parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RustType {
+    /// A named type, `name` is the last path segment and `generics` its type arguments.
+    Path {
+        name: String,
+        generics: Vec<RustType>,
+    },
+    Reference(Box<RustType>),
+    Slice(Box<RustType>),
+    Array(Box<RustType>),
+    Tuple(Vec<RustType>),
+    /// Any type that has no simplified form, kept as written.
+    Other(String),
+}
+
+impl RustType {
+    pub(crate) fn from_syn(ty: &Type) -> Self {
+        match ty {
+            Type::Path(type_path) => match type_path.path.segments.last() {
+                Some(segment) => {
+                    let generics = match &segment.arguments {
+                        PathArguments::AngleBracketed(args) => args
+                            .args
+                            .iter()
+                            .filter_map(|arg| match arg {
+                                GenericArgument::Type(ty) => Some(Self::from_syn(ty)),
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    Self::Path {
+                        name: segment.ident.to_string(),
+                        generics,
+                    }
+                }
+                None => Self::Other(ty.to_token_stream().to_string()),
+            },
+            Type::Reference(reference) => {
+                Self::Reference(Box::new(Self::from_syn(&reference.elem)))
+            }
+            Type::Slice(slice) => Self::Slice(Box::new(Self::from_syn(&slice.elem))),
+            Type::Array(array) => Self::Array(Box::new(Self::from_syn(&array.elem))),
+            Type::Tuple(tuple) => Self::Tuple(tuple.elems.iter().map(Self::from_syn).collect()),
+            Type::Paren(paren) => Self::from_syn(&paren.elem),
+            Type::Group(group) => Self::from_syn(&group.elem),
+            _ => Self::Other(ty.to_token_stream().to_string()),
+        }
+    }
+
+    /// Returns the type behind any number of references.
+    pub(crate) fn dereferenced(&self) -> &Self {
+        match self {
+            Self::Reference(inner) => inner.dereferenced(),
+            _ => self,
+        }
+    }
+
+    /// Splits a `Result<T, E>` into its `Ok` and `Err` types, any other type is returned as is.
+    ///
+    /// Errors of type aliases such as `tauri::Result<T>` are unknown and returned as `None`.
+    pub(crate) fn split_result(&self) -> (&Self, Option<&Self>) {
+        match self {
+            Self::Path { name, generics } if name == "Result" && !generics.is_empty() => {
+                (&generics[0], generics.get(1))
+            }
+            _ => (self, None),
+        }
+    }
+
+    /// Returns `true` if the type is one that Tauri injects into commands.
+    pub(crate) fn is_injected(&self) -> bool {
+        matches!(self.dereferenced(), Self::Path { name, .. } if INJECTED_TYPES.contains(&name.as_str()))
+    }
+}
+
+impl fmt::Display for RustType {
+    /// Writes the type as it would be written in Rust, without the paths of named types.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path { name, generics } if generics.is_empty() => f.write_str(name),
+            Self::Path { name, generics } => {
+                let generics: Vec<String> = generics.iter().map(ToString::to_string).collect();
+                write!(f, "{}<{}>", name, generics.join(", "))
+            }
+            Self::Reference(inner) => write!(f, "&{}", inner),
+            Self::Slice(inner) => write!(f, "[{}]", inner),
+            Self::Array(inner) => write!(f, "[{}; _]", inner),
+            Self::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(ToString::to_string).collect();
+                write!(f, "({})", items.join(", "))
+            }
+            Self::Other(ty) => f.write_str(ty),
+        }
+    }
+}
+
+/// An argument of a command.
+#[derive(Debug, Clone)]
+pub(crate) struct CommandArg {
+    pub name: String,
+    pub ty: RustType,
+}
+
+/// The parts of a command signature used by the generators.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommandSignature {
+    pub args: Vec<CommandArg>,
+    /// Return type of the command, `None` if it returns `()`.
+    pub output: Option<RustType>,
+    /// The signature as written, printed from its tokens, `None` when unknown.
+    pub tokens: Option<String>,
+}
+
+impl CommandSignature {
+    pub(crate) fn from_syn(sig: &Signature) -> Self {
+        let args = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(pat_type) => {
+                    let name = match &*pat_type.pat {
+                        Pat::Ident(pat) => pat.ident.to_string(),
+                        pat => pat.to_token_stream().to_string(),
+                    };
+                    Some(CommandArg {
+                        name,
+                        ty: RustType::from_syn(&pat_type.ty),
+                    })
+                }
+                FnArg::Receiver(_) => None,
+            })
+            .collect();
+
+        let output = match &sig.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some(RustType::from_syn(ty)),
+        };
+
+        Self {
+            args,
+            output,
+            tokens: Some(sig.to_token_stream().to_string()),
+        }
+    }
+
+    /// Returns the arguments sent by the frontend, skipping the ones injected by Tauri.
+    pub(crate) fn frontend_args(&self) -> impl Iterator<Item = &CommandArg> {
+        self.args.iter().filter(|arg| !arg.ty.is_injected())
+    }
+
+    /// Returns the type the command resolves with and the type of the error it rejects with:
+    /// `Result` return types are split, see [`RustType::split_result`], and commands returning
+    /// nothing resolve with `()`.
+    pub(crate) fn resolved(&self) -> (&RustType, Option<&RustType>) {
+        match &self.output {
+            Some(output) => output.split_result(),
+            None => (&UNIT, None),
+        }
+    }
+}
+
+static UNIT: RustType = RustType::Tuple(Vec::new());
+
+/// A struct with named fields found while scanning, used to describe the values commands return.
+#[derive(Debug, Clone)]
+pub(crate) struct StructDef {
+    pub name: String,
+    /// Fields as serialized, `#[serde(skip)]` ones excluded.
+    pub fields: Vec<StructField>,
+}
+
+/// A field of a [`StructDef`].
+#[derive(Debug, Clone)]
+pub(crate) struct StructField {
+    /// Key of the field once serialized, honoring `#[serde(rename)]` and `rename_all = "camelCase"`.
+    pub name: String,
+    pub ty: RustType,
+}
+
+impl StructDef {
+    /// Returns the struct if it has named fields, tuple and unit structs are skipped.
+    pub(crate) fn from_syn(item: &ItemStruct) -> Option<Self> {
+        let Fields::Named(named) = &item.fields else {
+            return None;
+        };
+
+        let mut camel_case = false;
+        for attr in item.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    camel_case = value.value() == "camelCase";
+                } else if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+        }
+
+        let fields = named
+            .named
+            .iter()
+            .filter_map(|field| {
+                let ident = field.ident.as_ref()?.to_string();
+                let mut name = if camel_case {
+                    to_camel_case(&ident)
+                } else {
+                    ident.trim_start_matches("r#").to_string()
+                };
+                let mut skip = false;
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+                    let _ = attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("rename") {
+                            let value: LitStr = meta.value()?.parse()?;
+                            name = value.value();
+                        } else if meta.path.is_ident("skip")
+                            || meta.path.is_ident("skip_serializing")
+                        {
+                            skip = true;
+                        } else if meta.input.peek(Token![=]) {
+                            meta.value()?.parse::<syn::Expr>()?;
+                        }
+                        Ok(())
+                    });
+                }
+                (!skip).then(|| StructField {
+                    name,
+                    ty: RustType::from_syn(&field.ty),
+                })
+            })
+            .collect();
+
+        Some(Self {
+            name: item.ident.to_string(),
+            fields,
+        })
+    }
+}
+
+/// Converts a `snake_case` argument name into the `camelCase` key expected by Tauri on the frontend.
+pub(crate) fn to_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' && !out.is_empty() {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,86 @@
+use rusqlite::{Connection, params};
+use std::path::Path;
+
+use crate::scan::WorkspaceScan;
+
+const CREATE_TABLE: &str = "CREATE TABLE commands (path TEXT NOT NULL, crate TEXT NOT NULL, \
+    name TEXT NOT NULL, aliases TEXT NOT NULL, platform TEXT, \"group\" TEXT, module TEXT, \
+    cfg TEXT NOT NULL, metadata_only INTEGER NOT NULL, no_args INTEGER NOT NULL, timeout_ms INTEGER)";
+
+/// Writes a SQLite database holding a `commands` table with a row per collected command:
+///
+/// ```sql
+/// SELECT crate, count(*) FROM commands WHERE metadata_only = 0 GROUP BY crate;
+/// ```
+///
+/// `aliases` and `cfg` hold JSON arrays, to be queried with `json_each`. The database must not
+/// exist yet.
+pub(crate) fn write_sqlite(db_file: &Path, scan: &WorkspaceScan) -> rusqlite::Result<()> {
+    let mut connection = Connection::open(db_file)?;
+    let transaction = connection.transaction()?;
+    transaction.execute(CREATE_TABLE, [])?;
+    {
+        let mut insert = transaction.prepare(
+            "INSERT INTO commands (path, crate, name, aliases, platform, \"group\", module, cfg, \
+            metadata_only, no_args, timeout_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        )?;
+        for member in &scan.members {
+            for command in &member.commands {
+                insert.execute(params![
+                    command.path,
+                    member.crate_name,
+                    command.exposed_name(),
+                    serde_json::to_string(&command.aliases).unwrap(),
+                    command.platform,
+                    command.group,
+                    command.module,
+                    serde_json::to_string(&command.cfg).unwrap(),
+                    command.metadata_only,
+                    command.no_args,
+                    command.timeout_ms,
+                ])?;
+            }
+        }
+    }
+    transaction.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn writes_a_row_per_command() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(group = "settings")]
+            pub fn set_theme(theme: String) {}
+            "#,
+        );
+        let db_file = fixture.root().join("commands.db");
+
+        write_sqlite(&db_file, &fixture.scan(&TauriHelperOptions::default())).unwrap();
+
+        let connection = Connection::open(&db_file).unwrap();
+        let count: i64 = connection
+            .query_row("SELECT count(*) FROM commands", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+        let group: String = connection
+            .query_row(
+                "SELECT \"group\" FROM commands WHERE name = 'set_theme'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(group, "settings");
+    }
+}
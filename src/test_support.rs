@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use tauri_helper_core::types::TauriHelperOptions;
+
+use crate::{WorkspaceScan, scan_workspace_at};
+
+/// A workspace written to a temporary directory, removed when dropped.
+///
+/// Each member is a crate named after its directory, its sources are added with [`file`](Self::file).
+pub(crate) struct Fixture {
+    dir: tempfile::TempDir,
+}
+
+impl Fixture {
+    /// Creates a workspace, whose package is `app`, with the given members.
+    pub(crate) fn workspace(members: &[&str]) -> Self {
+        let quoted: Vec<String> = members
+            .iter()
+            .map(|member| format!("{:?}", member))
+            .collect();
+        let mut fixture = Self {
+            dir: tempfile::tempdir().unwrap(),
+        }
+        .file(
+            "Cargo.toml",
+            &format!(
+                "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [{}]\n",
+                quoted.join(", ")
+            ),
+        );
+        for member in members {
+            let manifest = format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", member);
+            fixture = fixture.file(&format!("{}/Cargo.toml", member), &manifest);
+        }
+        fixture
+    }
+
+    /// Writes a file of the workspace, `path` being relative to its root.
+    pub(crate) fn file(self, path: &str, contents: &str) -> Self {
+        let path = self.root().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+        self
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Scans the workspace with the given options.
+    pub(crate) fn scan(&self, options: &TauriHelperOptions) -> WorkspaceScan {
+        scan_workspace_at(self.root(), options)
+    }
+
+    /// Returns the paths of the commands collected with the given options, in the order of the
+    /// command files.
+    pub(crate) fn command_paths(&self, options: &TauriHelperOptions) -> Vec<String> {
+        self.scan(options)
+            .members
+            .iter()
+            .flat_map(|member| &member.commands)
+            .map(|command| command.path.clone())
+            .collect()
+    }
+}
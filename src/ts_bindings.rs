@@ -0,0 +1,308 @@
+use crate::scan::WorkspaceScan;
+use crate::signature::{RustType, to_camel_case};
+
+/// Words that can't name a TypeScript function or parameter.
+const RESERVED_WORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Generates a TypeScript module exporting a typed wrapper around `invoke` for every command,
+/// named after the name it is registered under:
+///
+/// ```ts
+/// export const greet = (name: string): Promise<string> => invoke("greet", { name });
+/// ```
+///
+/// Arguments injected by Tauri are skipped and `Result` return types are unwrapped to their `Ok`
+/// type. Types other than primitives, collections, options and tuples are typed as `any`.
+pub(crate) fn generate_ts_bindings(scan: &WorkspaceScan) -> String {
+    let mut commands: Vec<_> = scan
+        .members
+        .iter()
+        .flat_map(|member| member.commands.iter().zip(&member.scanned))
+        .filter(|(info, _)| !info.metadata_only)
+        .collect();
+    commands.sort_by(|a, b| a.0.exposed_name().cmp(b.0.exposed_name()));
+
+    let mut out = String::from(
+        "// Generated by tauri-helper, do not edit.\nimport { invoke } from \"@tauri-apps/api/core\";\n",
+    );
+
+    for (info, command) in commands {
+        let mut params = Vec::new();
+        let mut fields = Vec::new();
+        for arg in command.signature.frontend_args() {
+            let key = to_camel_case(&arg.name);
+            let param = identifier(&key);
+            params.push(format!("{}: {}", param, ts_type(&arg.ty)));
+            fields.push(if param == key {
+                key
+            } else {
+                format!("{}: {}", key, param)
+            });
+        }
+
+        let (resolved, error) = command.signature.resolved();
+        let resolved = match resolved {
+            RustType::Tuple(items) if items.is_empty() => "void".to_string(),
+            resolved => ts_type(resolved),
+        };
+
+        out.push('\n');
+        if let Some(error) = error {
+            out.push_str(&format!("/** Rejects with `{}`. */\n", error));
+        }
+        let args = if fields.is_empty() {
+            String::new()
+        } else {
+            format!(", {{ {} }}", fields.join(", "))
+        };
+        out.push_str(&format!(
+            "export const {} = ({}): Promise<{}> => invoke(\"{}\"{});\n",
+            identifier(&to_camel_case(info.exposed_name())),
+            params.join(", "),
+            resolved,
+            info.exposed_name(),
+            args
+        ));
+    }
+
+    out
+}
+
+/// Generates a TypeScript module exporting the names every command can be invoked with, aliases
+/// included, and their union type:
+///
+/// ```ts
+/// export const COMMANDS = ["greet", "load_user"] as const;
+/// export type CommandName = (typeof COMMANDS)[number];
+/// ```
+pub(crate) fn generate_command_names(scan: &WorkspaceScan) -> String {
+    let mut names: Vec<&str> = scan
+        .commands()
+        .filter(|command| !command.metadata_only)
+        .flat_map(|command| {
+            std::iter::once(command.exposed_name())
+                .chain(command.aliases.iter().map(String::as_str))
+        })
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let names: Vec<String> = names.iter().map(|name| format!("{:?}", name)).collect();
+    format!(
+        "// Generated by tauri-helper, do not edit.\nexport const COMMANDS = [{}] as const;\nexport type CommandName = (typeof COMMANDS)[number];\n",
+        names.join(", ")
+    )
+}
+
+/// Appends an underscore to reserved words, e.g. `delete_`.
+fn identifier(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn ts_type(ty: &RustType) -> String {
+    match ty.dereferenced() {
+        RustType::Path { name, generics } => match (name.as_str(), generics.as_slice()) {
+            ("String" | "str" | "char" | "PathBuf" | "Path", _) => "string".to_string(),
+            (
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize" | "f32" | "f64",
+                _,
+            ) => "number".to_string(),
+            ("bool", _) => "boolean".to_string(),
+            ("Option", [inner]) => format!("{} | null", ts_type(inner)),
+            ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => array(inner),
+            ("HashMap" | "BTreeMap", [_, value]) => format!("Record<string, {}>", ts_type(value)),
+            ("Box" | "Rc" | "Arc" | "Cow", [inner]) => ts_type(inner),
+            _ => "any".to_string(),
+        },
+        RustType::Slice(inner) | RustType::Array(inner) => array(inner),
+        RustType::Tuple(items) if items.is_empty() => "null".to_string(),
+        RustType::Tuple(items) => format!(
+            "[{}]",
+            items.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+        _ => "any".to_string(),
+    }
+}
+
+fn array(inner: &RustType) -> String {
+    let inner = ts_type(inner);
+    // `string | null[]` would be a union with an array
+    if inner.contains(' ') {
+        format!("({})[]", inner)
+    } else {
+        format!("{}[]", inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn result_commands_resolve_with_their_ok_type() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub async fn load_user(id: u32) -> Result<Vec<String>, MyError> {
+                Ok(Vec::new())
+            }
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn save_user(state: State<'_, AppState>, user_name: String) -> Result<(), String> {
+                Ok(())
+            }
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            generate_ts_bindings(&scan),
+            r#"// Generated by tauri-helper, do not edit.
+import { invoke } from "@tauri-apps/api/core";
+
+/** Rejects with `MyError`. */
+export const loadUser = (id: number): Promise<string[]> => invoke("load_user", { id });
+
+/** Rejects with `String`. */
+export const saveUser = (userName: string): Promise<void> => invoke("save_user", { userName });
+"#
+        );
+    }
+
+    #[test]
+    fn the_name_prefix_is_applied_to_every_command() {
+        let fixture = Fixture::workspace(&["cmds", "docs"])
+            .file(
+                "cmds/src/lib.rs",
+                r#"
+                #[tauri::command]
+                #[auto_collect_command(rename = "hello")]
+                pub fn greet() {}
+
+                #[tauri::command]
+                #[auto_collect_command(alias = "fetch_user")]
+                pub fn load_user(id: u32) {}
+                "#,
+            )
+            .file(
+                "docs/src/lib.rs",
+                "#[tauri::command]\n#[auto_collect_command]\npub fn describe() {}\n",
+            );
+        let options = TauriHelperOptions::builder()
+            .name_prefix("app_")
+            .metadata_only_crate("docs")
+            .build();
+
+        let scan = fixture.scan(&options);
+        let names: Vec<&str> = scan
+            .commands()
+            .map(|command| command.exposed_name())
+            .collect();
+        // metadata-only commands are registered elsewhere, under their own name
+        assert_eq!(names, ["app_hello", "app_load_user", "describe"]);
+        assert_eq!(
+            generate_command_names(&scan),
+            r#"// Generated by tauri-helper, do not edit.
+export const COMMANDS = ["app_fetch_user", "app_hello", "app_load_user"] as const;
+export type CommandName = (typeof COMMANDS)[number];
+"#
+        );
+        assert_eq!(
+            generate_ts_bindings(&scan),
+            r#"// Generated by tauri-helper, do not edit.
+import { invoke } from "@tauri-apps/api/core";
+
+export const appHello = (): Promise<void> => invoke("app_hello");
+
+export const appLoadUser = (id: number): Promise<void> => invoke("app_load_user", { id });
+"#
+        );
+    }
+
+    #[test]
+    fn simple_commands_get_a_typed_wrapper() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet(name: String) -> String {
+                format!("Hello, {}!", name)
+            }
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn set_volume(level: f32, muted: bool, tags: Vec<String>, config: Config) {}
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            generate_ts_bindings(&scan),
+            r#"// Generated by tauri-helper, do not edit.
+import { invoke } from "@tauri-apps/api/core";
+
+export const greet = (name: string): Promise<string> => invoke("greet", { name });
+
+export const setVolume = (level: number, muted: boolean, tags: string[], config: any): Promise<void> => invoke("set_volume", { level, muted, tags, config });
+"#
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::{fmt, fs};
+use tauri_helper_core::command::{CommandInfo, command_files, read_command_file};
+use tauri_helper_core::types::TauriHelperOptions;
+use tauri_helper_core::{commands_dir_at, current_workspace_dir};
+
+use crate::{lockfile, scan_workspace_at};
+
+/// A difference between the command files and the commands currently found in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The command exists in the source but not in the command file.
+    Added { crate_name: String, path: String },
+    /// The command is in the command file but no longer exists in the source.
+    Removed { crate_name: String, path: String },
+    /// The command is exposed under another name than the one in the command file.
+    Renamed {
+        crate_name: String,
+        path: String,
+        from: String,
+        to: String,
+    },
+    /// The metadata of the command (platform, group, module or cfg) changed.
+    Changed { crate_name: String, path: String },
+    /// The signature of the command differs from the one pinned in the signature lock.
+    SignatureChanged { crate_name: String, path: String },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { crate_name, path } => {
+                write!(
+                    f,
+                    "{}: `{}` is missing from the command file",
+                    crate_name, path
+                )
+            }
+            Self::Removed { crate_name, path } => {
+                write!(
+                    f,
+                    "{}: `{}` no longer exists in the source",
+                    crate_name, path
+                )
+            }
+            Self::Renamed {
+                crate_name,
+                path,
+                from,
+                to,
+            } => write!(
+                f,
+                "{}: `{}` is exposed as `{}` but the command file says `{}`",
+                crate_name, path, to, from
+            ),
+            Self::Changed { crate_name, path } => write!(
+                f,
+                "{}: the metadata of `{}` differs from the command file",
+                crate_name, path
+            ),
+            Self::SignatureChanged { crate_name, path } => write!(
+                f,
+                "{}: the signature of `{}` differs from the signature lock",
+                crate_name, path
+            ),
+        }
+    }
+}
+
+/// Re-scans the workspace with the default options and compares the result with the command
+/// files written by the last build.
+///
+/// See [`validate_command_files_with`].
+pub fn validate_command_files() -> Result<(), Vec<Discrepancy>> {
+    validate_command_files_with(&TauriHelperOptions::default())
+}
+
+/// Re-scans the workspace and compares the result with the command files written by the last
+/// build, meant for CI checks or build scripts that want to fail on stale command files.
+///
+/// The options should be the ones given to [`generate_command_file`](crate::generate_command_file),
+/// otherwise every command they affect is reported. When `signature_lock` is set and the lock
+/// exists, the signatures of the commands it pins are compared with the current ones as well.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`scan_workspace`](crate::scan_workspace).
+pub fn validate_command_files_with(options: &TauriHelperOptions) -> Result<(), Vec<Discrepancy>> {
+    validate_command_files_at(&current_workspace_dir(), options)
+}
+
+/// [`validate_command_files_with`] for the workspace at `workspace_root`.
+pub(crate) fn validate_command_files_at(
+    workspace_root: &Path,
+    options: &TauriHelperOptions,
+) -> Result<(), Vec<Discrepancy>> {
+    let scan = scan_workspace_at(workspace_root, options);
+    let commands_dir = commands_dir_at(&scan.workspace_root);
+
+    let mut written: BTreeMap<String, Vec<CommandInfo>> = BTreeMap::new();
+    for path in command_files(&commands_dir).unwrap_or_default() {
+        if let Some(crate_name) = path.file_stem().and_then(|s| s.to_str()) {
+            written.insert(
+                crate_name.to_string(),
+                read_command_file(&path).unwrap_or_default(),
+            );
+        }
+    }
+
+    let current = scan.crates();
+    let crate_names: BTreeSet<&str> = current
+        .keys()
+        .copied()
+        .chain(written.keys().map(String::as_str))
+        .collect();
+
+    let mut discrepancies = Vec::new();
+    for crate_name in crate_names {
+        let current = current.get(crate_name).copied().unwrap_or_default();
+        let written = written
+            .get(crate_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        compare(crate_name, written, current, &mut discrepancies);
+    }
+
+    if let Some(signature_lock) = &options.signature_lock
+        && scan.signatures_known
+        && let Ok(contents) = fs::read_to_string(scan.workspace_root.join(signature_lock))
+    {
+        let pinned = lockfile::read_lockfile(&contents);
+        let current = lockfile::signature_hashes(&scan);
+        for member in &scan.members {
+            for command in &member.commands {
+                if let (Some(pinned), Some(current)) = (
+                    pinned.get(&command.path),
+                    current.get(command.path.as_str()),
+                ) && pinned != current
+                {
+                    discrepancies.push(Discrepancy::SignatureChanged {
+                        crate_name: member.crate_name.clone(),
+                        path: command.path.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if discrepancies.is_empty() {
+        Ok(())
+    } else {
+        Err(discrepancies)
+    }
+}
+
+fn compare(
+    crate_name: &str,
+    written: &[CommandInfo],
+    current: &[CommandInfo],
+    discrepancies: &mut Vec<Discrepancy>,
+) {
+    let written: BTreeMap<&str, &CommandInfo> =
+        written.iter().map(|c| (c.path.as_str(), c)).collect();
+    let current: BTreeMap<&str, &CommandInfo> =
+        current.iter().map(|c| (c.path.as_str(), c)).collect();
+
+    for (path, command) in &current {
+        let Some(old) = written.get(path) else {
+            discrepancies.push(Discrepancy::Added {
+                crate_name: crate_name.to_string(),
+                path: path.to_string(),
+            });
+            continue;
+        };
+
+        if old.exposed_name() != command.exposed_name() {
+            discrepancies.push(Discrepancy::Renamed {
+                crate_name: crate_name.to_string(),
+                path: path.to_string(),
+                from: old.exposed_name().to_string(),
+                to: command.exposed_name().to_string(),
+            });
+        } else if old != command {
+            discrepancies.push(Discrepancy::Changed {
+                crate_name: crate_name.to_string(),
+                path: path.to_string(),
+            });
+        }
+    }
+
+    for path in written.keys().filter(|path| !current.contains_key(*path)) {
+        discrepancies.push(Discrepancy::Removed {
+            crate_name: crate_name.to_string(),
+            path: path.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use crate::write_command_files_at;
+
+    #[test]
+    fn stale_command_files_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn save_user() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn load_user() {}
+            "#,
+        );
+        let options = TauriHelperOptions::default();
+        write_command_files_at(fixture.root(), &options);
+        assert_eq!(validate_command_files_at(fixture.root(), &options), Ok(()));
+
+        let fixture = fixture.file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command(rename = "hello")]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command(group = "users")]
+            pub fn load_user() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn delete_user() {}
+            "#,
+        );
+        let crate_name = "cmds".to_string();
+        assert_eq!(
+            validate_command_files_at(fixture.root(), &options),
+            Err(vec![
+                Discrepancy::Added {
+                    crate_name: crate_name.clone(),
+                    path: "cmds::delete_user".to_string(),
+                },
+                Discrepancy::Renamed {
+                    crate_name: crate_name.clone(),
+                    path: "cmds::greet".to_string(),
+                    from: "greet".to_string(),
+                    to: "hello".to_string(),
+                },
+                Discrepancy::Changed {
+                    crate_name: crate_name.clone(),
+                    path: "cmds::load_user".to_string(),
+                },
+                Discrepancy::Removed {
+                    crate_name,
+                    path: "cmds::save_user".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn signature_changes_are_reported() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn load_user(id: u32) -> String { id.to_string() }
+            "#,
+        );
+        let options = TauriHelperOptions::builder()
+            .signature_lock("tauri-commands.lock")
+            .build();
+        write_command_files_at(fixture.root(), &options);
+        assert!(fixture.root().join("tauri-commands.lock").is_file());
+
+        // formatting doesn't change the signature
+        let fixture = fixture.file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet( ) {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn load_user(
+                // the id of the user
+                id: u32
+            ) -> String {
+                id.to_string()
+            }
+            "#,
+        );
+        assert_eq!(validate_command_files_at(fixture.root(), &options), Ok(()));
+
+        let fixture = fixture.file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn greet() {}
+
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn load_user(id: u64) -> String { id.to_string() }
+            "#,
+        );
+        assert_eq!(
+            validate_command_files_at(fixture.root(), &options),
+            // the command files record the signature too
+            Err(vec![
+                Discrepancy::Changed {
+                    crate_name: "cmds".to_string(),
+                    path: "cmds::load_user".to_string(),
+                },
+                Discrepancy::SignatureChanged {
+                    crate_name: "cmds".to_string(),
+                    path: "cmds::load_user".to_string(),
+                },
+            ])
+        );
+    }
+}
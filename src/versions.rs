@@ -0,0 +1,89 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Checks the versions of `tauri`, `tauri-specta` and `specta` resolved in a `Cargo.lock`,
+/// returning a warning for each combination known not to work together.
+///
+/// `tauri-specta` 1 targets `tauri` 1 and `specta` 1, `tauri-specta` 2 targets `tauri` 2 and
+/// `specta` 2. Mixing them makes `specta_collect_commands!` fail with confusing trait errors.
+pub(crate) fn version_warnings(lockfile: &str) -> Vec<String> {
+    let Ok(lockfile) = toml::from_str::<toml::Table>(lockfile) else {
+        return Vec::new();
+    };
+
+    // every resolved major version of the crates involved, several of them can coexist
+    let mut majors: BTreeMap<&str, BTreeSet<(u64, &str)>> = BTreeMap::new();
+    let packages = lockfile.get("package").and_then(toml::Value::as_array);
+    for package in packages.into_iter().flatten() {
+        let name = package.get("name").and_then(toml::Value::as_str);
+        let version = package.get("version").and_then(toml::Value::as_str);
+        if let (Some(name @ ("tauri" | "tauri-specta" | "specta")), Some(version)) = (name, version)
+            && let Some(major) = version.split('.').next().and_then(|m| m.parse().ok())
+        {
+            majors.entry(name).or_default().insert((major, version));
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let Some(tauri_specta) = majors.get("tauri-specta") else {
+        return warnings;
+    };
+    for &(major, version) in tauri_specta {
+        for dependency in ["tauri", "specta"] {
+            for &(other_major, other_version) in majors.get(dependency).into_iter().flatten() {
+                if other_major != major {
+                    warnings.push(format!(
+                        "tauri-specta {} is used with {} {}, they are incompatible and `specta_collect_commands!` will fail with trait errors. Use tauri-specta {} with {} {}",
+                        version, dependency, other_version, major, dependency, major
+                    ));
+                }
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lockfile(packages: &[(&str, &str)]) -> String {
+        packages
+            .iter()
+            .map(|(name, version)| {
+                format!(
+                    "[[package]]\nname = \"{}\"\nversion = \"{}\"\n\n",
+                    name, version
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn incompatible_versions_are_reported() {
+        let lockfile = lockfile(&[
+            ("specta", "2.0.0-rc.22"),
+            ("tauri", "1.8.1"),
+            ("tauri-specta", "2.0.0-rc.21"),
+        ]);
+
+        assert_eq!(
+            version_warnings(&lockfile),
+            [
+                "tauri-specta 2.0.0-rc.21 is used with tauri 1.8.1, they are incompatible and `specta_collect_commands!` will fail with trait errors. Use tauri-specta 2 with tauri 2"
+            ]
+        );
+    }
+
+    #[test]
+    fn compatible_versions_are_not_reported() {
+        let matching = lockfile(&[
+            ("specta", "2.0.0-rc.22"),
+            ("tauri", "2.5.0"),
+            ("tauri-specta", "2.0.0-rc.21"),
+        ]);
+        assert!(version_warnings(&matching).is_empty());
+
+        // without tauri-specta, nothing is checked
+        assert!(version_warnings(&lockfile(&[("tauri", "1.8.1"), ("specta", "2.0.0")])).is_empty());
+    }
+}
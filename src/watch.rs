@@ -0,0 +1,160 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+use tauri_helper_core::types::TauriHelperOptions;
+use tauri_helper_core::{current_workspace_dir, get_workspace_members};
+
+use crate::write_command_files_at;
+
+/// How long to wait for the other events of a change, editors often write a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Watches the sources of the workspace members and regenerates the command files whenever one
+/// of them changes, so that the command list stays up to date during `tauri dev`.
+///
+/// This is meant to run in a dev tool started with `cargo run` (it needs `CARGO_MANIFEST_DIR` to
+/// find the workspace), not in `build.rs`. It never returns.
+///
+/// ```rust,ignore
+/// fn main() {
+///     tauri_helper::watch_and_regenerate(tauri_helper::TauriHelperOptions::default());
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the member directories can't be watched.
+pub fn watch_and_regenerate(options: TauriHelperOptions) -> ! {
+    let watch = SourceWatch::start(current_workspace_dir(), options)
+        .unwrap_or_else(|e| panic!("Failed to watch the workspace members: {}", e));
+
+    loop {
+        watch.regenerate_on_change(None);
+    }
+}
+
+/// The command files of a workspace, kept up to date with its sources.
+struct SourceWatch {
+    workspace_root: PathBuf,
+    options: TauriHelperOptions,
+    changes: Receiver<notify::Result<Event>>,
+    // dropping the watcher stops the notifications
+    _watcher: RecommendedWatcher,
+}
+
+impl SourceWatch {
+    /// Writes the command files and starts watching the member directories.
+    fn start(workspace_root: PathBuf, options: TauriHelperOptions) -> notify::Result<Self> {
+        let (sender, changes) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        let members = options
+            .members
+            .clone()
+            .unwrap_or_else(|| get_workspace_members(&workspace_root));
+        for member in members {
+            watcher.watch(&workspace_root.join(member), RecursiveMode::Recursive)?;
+        }
+
+        write_command_files_at(&workspace_root, &options);
+        Ok(Self {
+            workspace_root,
+            options,
+            changes,
+            _watcher: watcher,
+        })
+    }
+
+    /// Waits for a Rust source to change and regenerates the command files, returns `false` if
+    /// nothing changed within `timeout`.
+    fn regenerate_on_change(&self, timeout: Option<Duration>) -> bool {
+        loop {
+            let event = match timeout {
+                Some(timeout) => match self.changes.recv_timeout(timeout) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => return false,
+                    Err(RecvTimeoutError::Disconnected) => panic!("The source watcher stopped"),
+                },
+                None => self
+                    .changes
+                    .recv()
+                    .unwrap_or_else(|_| panic!("The source watcher stopped")),
+            };
+            if event.as_ref().is_ok_and(is_source_change) {
+                break;
+            }
+        }
+        // the rest of the change, whatever it touched, is covered by the same regeneration
+        while self.changes.recv_timeout(DEBOUNCE).is_ok() {}
+
+        println!("tauri-helper: sources changed, regenerating the command files");
+        write_command_files_at(&self.workspace_root, &self.options);
+        true
+    }
+}
+
+/// Returns `true` for events creating, modifying or removing a Rust source outside of the build
+/// outputs, such as the command files themselves.
+fn is_source_change(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| is_source(path))
+}
+
+fn is_source(path: &Path) -> bool {
+    path.extension().and_then(|x| x.to_str()) == Some("rs")
+        // members can be nested in each other, never react to build outputs
+        && !path
+            .components()
+            .any(|component| component == Component::Normal("target".as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use std::fs;
+    use tauri_helper_core::command::read_command_file;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn changing_a_source_regenerates_the_command_files() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n",
+        );
+        let command_file = fixture.root().join("target/tauri_commands_list/cmds.txt");
+
+        let watch = SourceWatch::start(fixture.root().to_path_buf(), TauriHelperOptions::default())
+            .unwrap();
+        let paths = || -> Vec<String> {
+            read_command_file(&command_file)
+                .unwrap()
+                .into_iter()
+                .map(|command| command.path)
+                .collect()
+        };
+        assert_eq!(paths(), ["cmds::greet"]);
+
+        fs::write(
+            fixture.root().join("cmds/src/lib.rs"),
+            "#[tauri::command]\n#[auto_collect_command]\npub fn greet() {}\n\n\
+            #[tauri::command]\n#[auto_collect_command]\npub fn farewell() {}\n",
+        )
+        .unwrap();
+
+        assert!(watch.regenerate_on_change(Some(TIMEOUT)));
+        assert_eq!(paths(), ["cmds::greet", "cmds::farewell"]);
+    }
+
+    #[test]
+    fn build_outputs_are_not_sources() {
+        assert!(is_source(Path::new("/app/src/lib.rs")));
+        assert!(!is_source(Path::new(
+            "/app/target/debug/build/out/generated.rs"
+        )));
+        assert!(!is_source(Path::new("/app/src/notes.md")));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::scan::MemberScan;
+use crate::signature::{RustType, to_camel_case};
+
+/// Generates a TypeScript module exporting Zod schemas for the arguments and the result of every
+/// command of a member, named after the name the command is registered under.
+///
+/// A `<command>Result` schema describes the value each command resolves with, `Result` return
+/// types are unwrapped to their `Ok` type. Commands with a `timeout_ms` also export it as
+/// `<command>TimeoutMs`.
+///
+/// Types other than primitives, collections, options and tuples are described as `z.unknown()`.
+pub(crate) fn generate_zod_schemas(member: &MemberScan) -> String {
+    let mut out =
+        String::from("// Generated by tauri-helper, do not edit.\nimport { z } from \"zod\";\n");
+
+    for (info, command) in member.commands.iter().zip(&member.scanned) {
+        let name = to_camel_case(info.exposed_name());
+        let fields = command
+            .signature
+            .frontend_args()
+            .map(|arg| format!("  {}: {},\n", to_camel_case(&arg.name), zod_type(&arg.ty)))
+            .collect::<String>();
+
+        if fields.is_empty() {
+            out.push_str(&format!("\nexport const {}Args = z.object({{}});\n", name));
+        } else {
+            out.push_str(&format!(
+                "\nexport const {}Args = z.object({{\n{}}});\n",
+                name, fields
+            ));
+        }
+
+        let (resolved, error) = command.signature.resolved();
+        if let Some(error) = error {
+            out.push_str(&format!("/** Rejects with `{}`. */\n", error));
+        }
+        out.push_str(&format!(
+            "export const {}Result = {};\n",
+            name,
+            zod_type(resolved)
+        ));
+        if let Some(timeout_ms) = command.args.timeout_ms {
+            out.push_str(&format!(
+                "export const {}TimeoutMs = {};\n",
+                name, timeout_ms
+            ));
+        }
+    }
+
+    out
+}
+
+fn zod_type(ty: &RustType) -> String {
+    match ty.dereferenced() {
+        RustType::Path { name, generics } => match (name.as_str(), generics.as_slice()) {
+            ("String" | "str" | "char" | "PathBuf" | "Path", _) => "z.string()".to_string(),
+            (
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize" | "f32" | "f64",
+                _,
+            ) => "z.number()".to_string(),
+            ("bool", _) => "z.boolean()".to_string(),
+            ("Option", [inner]) => format!("{}.nullish()", zod_type(inner)),
+            ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+                format!("z.array({})", zod_type(inner))
+            }
+            ("HashMap" | "BTreeMap", [_, value]) => {
+                format!("z.record(z.string(), {})", zod_type(value))
+            }
+            ("Box" | "Rc" | "Arc" | "Cow", [inner]) => zod_type(inner),
+            _ => "z.unknown()".to_string(),
+        },
+        RustType::Slice(inner) | RustType::Array(inner) => format!("z.array({})", zod_type(inner)),
+        RustType::Tuple(items) if items.is_empty() => "z.null()".to_string(),
+        RustType::Tuple(items) => format!(
+            "z.tuple([{}])",
+            items.iter().map(zod_type).collect::<Vec<_>>().join(", ")
+        ),
+        _ => "z.unknown()".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Fixture;
+    use tauri_helper_core::types::TauriHelperOptions;
+
+    #[test]
+    fn schema_of_a_simple_argument_set() {
+        let fixture = Fixture::workspace(&["cmds"]).file(
+            "cmds/src/lib.rs",
+            r#"
+            #[tauri::command]
+            #[auto_collect_command]
+            pub fn save_user(
+                state: State<'_, AppState>,
+                user_name: String,
+                age: Option<u32>,
+                tags: Vec<String>,
+            ) -> Result<bool, String> {
+                Ok(true)
+            }
+            "#,
+        );
+
+        let scan = fixture.scan(&TauriHelperOptions::default());
+        assert_eq!(
+            generate_zod_schemas(&scan.members[0]),
+            r#"// Generated by tauri-helper, do not edit.
+import { z } from "zod";
+
+export const saveUserArgs = z.object({
+  userName: z.string(),
+  age: z.number().nullish(),
+  tags: z.array(z.string()),
+});
+/** Rejects with `String`. */
+export const saveUserResult = z.boolean();
+"#
+        );
+    }
+
+    #[test]
+    fn metadata_only_crates_have_schemas() {
+        let fixture = Fixture::workspace(&["docs"]).file(
+            "docs/src/lib.rs",
+            "#[tauri::command]\n#[auto_collect_command]\npub fn describe(topic: String) {}\n",
+        );
+        let options = TauriHelperOptions::builder()
+            .metadata_only_crate("docs")
+            .build();
+
+        let scan = fixture.scan(&options);
+        assert!(scan.members[0].commands[0].metadata_only);
+        assert!(
+            generate_zod_schemas(&scan.members[0])
+                .contains("export const describeArgs = z.object({\n  topic: z.string(),\n});")
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Runs `cargo tauri-helper` on a workspace written to a temporary directory.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn write(root: &Path, path: &str, contents: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn list_prints_the_commands_as_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "Cargo.toml",
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"users\"]\n",
+    );
+    write(
+        root,
+        "users/Cargo.toml",
+        "[package]\nname = \"users\"\nversion = \"0.1.0\"\n",
+    );
+    write(
+        root,
+        "users/src/lib.rs",
+        r#"
+        #[tauri::command]
+        #[auto_collect_command]
+        pub fn greet() {}
+
+        #[tauri::command]
+        #[auto_collect_command(group = "users")]
+        pub fn load_user(id: u32) {}
+        "#,
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-tauri-helper"))
+        .args(["tauri-helper", "list", "--json"])
+        // from a member, the workspace is found above it
+        .current_dir(root.join("users"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let commands = json["crates"]["users"].as_array().unwrap();
+    let paths: Vec<&str> = commands
+        .iter()
+        .map(|command| command["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["users::greet", "users::load_user"]);
+    assert_eq!(commands[1]["group"], "users");
+}
@@ -0,0 +1,104 @@
+//! Builds and tests the apps of `tests/fixtures`, whose build script writes the command files their
+//! macros then read, failing if they don't compile or if their own tests fail.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn cargo_test(fixture: &str, args: &[&str]) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    cargo_test_in(&root.join("tests/fixtures").join(fixture), fixture, args);
+}
+
+/// Runs the tests of the fixture located in `dir`.
+fn cargo_test_in(dir: &Path, fixture: &str, args: &[&str]) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = dir.join("Cargo.toml");
+    let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .args(args)
+        // from the fixture, so that its `.cargo/config.toml` applies
+        .current_dir(dir)
+        // the fixtures share their dependencies, the command files go in their own `target`
+        .env("CARGO_TARGET_DIR", root.join("target/fixtures"))
+        // the expansions of the macros must not raise warnings either
+        .env("RUSTFLAGS", "-D warnings")
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run cargo on {}: {}", manifest.display(), e));
+
+    assert!(
+        output.status.success(),
+        "the tests of the `{}` fixture failed:\n{}\n{}",
+        fixture,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn app() {
+    cargo_test("app", &[]);
+}
+
+#[test]
+fn app_with_features() {
+    cargo_test("app", &["--features", "admin"]);
+}
+
+#[test]
+fn committed() {
+    cargo_test("committed", &[]);
+}
+
+/// Copies `from` into `to`, recursively.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &to.join(entry.file_name()));
+        } else {
+            fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+        }
+    }
+}
+
+#[test]
+fn single_crate_app() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // out of this repository, since its workspace would be found above the fixture
+    let dir = tempfile::tempdir().unwrap();
+    let app = dir.path().join("src-tauri");
+    copy_dir(&root.join("tests/fixtures/single"), &app);
+    // the dependencies resolved to the same versions as the other fixtures
+    fs::copy(
+        root.join("tests/fixtures/app/Cargo.lock"),
+        app.join("Cargo.lock"),
+    )
+    .unwrap();
+    fs::write(
+        app.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"single\"\nversion = \"0.1.0\"\nedition = \"2024\"\npublish = false\n\n\
+            [dependencies]\ntauri-helper = {{ path = {root:?} }}\ntauri = {{ path = {tauri:?} }}\n\n\
+            [build-dependencies]\ntauri-helper = {{ path = {root:?} }}\n",
+            root = root,
+            tauri = root.join("tests/fixtures/app/tauri"),
+        ),
+    )
+    .unwrap();
+
+    cargo_test_in(&app, "single", &[]);
+    // one command file, named after the crate
+    let commands_dir = app.join("target/tauri_commands_list");
+    let files: Vec<_> = fs::read_dir(&commands_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|x| x == "txt"))
+        .map(|path| path.file_name().unwrap().to_owned())
+        .collect();
+    assert_eq!(files, ["src-tauri.txt"]);
+}
@@ -0,0 +1,9 @@
+fn main() {
+    tauri_helper::generate_command_file(
+        tauri_helper::TauriHelperOptions::builder()
+            .metadata_only_crate("docs")
+            .builder_snippet(true)
+            .test_stub(true)
+            .build(),
+    );
+}
@@ -0,0 +1,9 @@
+//! Commands only documented, `build.rs` marks the crate as metadata only.
+
+use tauri_helper::auto_collect_command;
+
+#[tauri::command]
+#[auto_collect_command]
+pub fn describe() -> String {
+    "Documented, never registered".to_string()
+}
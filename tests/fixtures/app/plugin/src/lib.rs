@@ -0,0 +1,7 @@
+use tauri_helper::auto_collect_command;
+
+#[tauri::command]
+#[auto_collect_command]
+pub fn greet() -> String {
+    "Hello from the plugin!".to_string()
+}
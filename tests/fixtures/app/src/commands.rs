@@ -0,0 +1,48 @@
+use tauri_helper::auto_collect_command;
+
+pub mod r#move;
+
+#[derive(Debug, PartialEq)]
+pub struct User {
+    pub id: u32,
+}
+
+#[tauri::command]
+#[auto_collect_command]
+pub fn greet(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[tauri::command]
+#[auto_collect_command(rename = "sum")]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[tauri::command]
+#[auto_collect_command(alias = "fetch_user")]
+pub async fn load_user(id: u32) -> Result<User, String> {
+    Ok(User { id })
+}
+
+#[tauri::command]
+#[auto_collect_command(platform = "mobile")]
+pub fn scan_barcode() {}
+
+#[tauri::command]
+#[auto_collect_command(platform = "desktop")]
+pub fn open_window() {}
+
+#[cfg(feature = "admin")]
+#[tauri::command]
+#[auto_collect_command]
+pub fn export_logs() {}
+
+#[cfg_attr(feature = "admin", tauri::command)]
+#[cfg_attr(feature = "admin", auto_collect_command)]
+pub fn purge_cache() {}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[auto_collect_command]
+pub fn quit() {}
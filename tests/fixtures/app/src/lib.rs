@@ -0,0 +1,245 @@
+//! An app collecting its commands and the ones of a plugin, built by the tests of tauri-helper.
+
+use tauri_helper::tauri_invoke_wrappers;
+
+pub mod commands;
+
+// the wrappers refer to the types of the commands as written in their signature
+use commands::User;
+
+tauri_invoke_wrappers!();
+
+// the `tauri_builder()` function, written by the build script
+include!(concat!(env!("OUT_DIR"), "/tauri_builder.rs"));
+
+/// The metadata of the collected commands, written by the build script.
+pub mod registry {
+    include!(concat!(env!("OUT_DIR"), "/tauri_commands.rs"));
+}
+
+// a test referring to every command, written by the build script
+#[cfg(test)]
+mod command_stubs {
+    include!(concat!(env!("OUT_DIR"), "/tauri_command_stubs.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Mutex;
+    use std::task::{Context, Poll, Waker};
+    use tauri_helper::{
+        array_collect_commands, tauri_collect_commands, tauri_collect_commands_for_platform,
+        tauri_collect_commands_with_fallback,
+    };
+
+    /// A module defining the handler, re-exported below.
+    mod handler {
+        tauri_helper::tauri_collect_commands!(pub);
+    }
+
+    use handler::__tauri_helper_generated::__tauri_collected_handler as collected_handler;
+
+    /// A module defining a handler with the built-in ping command.
+    mod pinged {
+        tauri_helper::tauri_collect_commands!(pub, inject_ping);
+    }
+
+    fn ready<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("the command is still pending"),
+        }
+    }
+
+    #[test]
+    fn platform_handlers_only_register_the_commands_of_their_platform() {
+        let mobile = tauri::Builder::default()
+            .invoke_handler(tauri_collect_commands_for_platform!("mobile"));
+        assert!(mobile.invoke("scan_barcode"));
+        assert!(!mobile.invoke("open_window"));
+        // commands without a platform are registered on every platform
+        assert!(mobile.invoke("sum"));
+
+        let desktop = tauri::Builder::default()
+            .invoke_handler(tauri_collect_commands_for_platform!("desktop"));
+        assert!(desktop.invoke("open_window"));
+        assert!(!desktop.invoke("scan_barcode"));
+        assert!(desktop.invoke("sum"));
+    }
+
+    #[test]
+    fn commands_are_invoked_by_their_alias() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(builder.invoke("fetch_user"));
+        assert!(builder.invoke("load_user"));
+        assert!(!builder.invoke("add"));
+    }
+
+    #[test]
+    fn unregistered_commands_are_handed_to_the_fallback() {
+        static UNKNOWN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        fn unknown_command(invoke: tauri::ipc::Invoke) -> bool {
+            UNKNOWN
+                .lock()
+                .unwrap()
+                .push(invoke.message.command().to_string());
+            true
+        }
+
+        let builder = tauri::Builder::default()
+            .invoke_handler(tauri_collect_commands_with_fallback!(unknown_command));
+        assert!(builder.invoke("sum"));
+        assert!(builder.invoke("fetch_user"));
+        assert!(builder.invoke("add"));
+        assert_eq!(*UNKNOWN.lock().unwrap(), ["add"]);
+    }
+
+    #[test]
+    fn the_array_lists_the_commands_of_the_handler() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        let registered: Vec<_> = registry::COMMANDS
+            .iter()
+            .filter(|command| command.path != "docs::describe")
+            .collect();
+        assert!(
+            registered
+                .iter()
+                .all(|command| builder.invoke(command.name))
+        );
+
+        // both strip the prefix of the calling crate
+        let mut paths: Vec<&str> = registered
+            .iter()
+            .map(|command| command.path.strip_prefix("app::").unwrap_or(command.path))
+            .collect();
+        paths.sort_unstable();
+        let mut array = array_collect_commands!();
+        array.sort_unstable();
+        assert_eq!(array, paths.as_slice());
+    }
+
+    #[test]
+    fn the_public_handler_can_be_re_exported() {
+        let builder = tauri::Builder::default().invoke_handler(collected_handler());
+        assert!(builder.invoke("sum"));
+        assert!(builder.invoke("fetch_user"));
+        assert!(!builder.invoke("describe"));
+    }
+
+    #[test]
+    fn the_ping_command_is_only_registered_when_requested() {
+        let builder = tauri::Builder::default()
+            .invoke_handler(pinged::__tauri_helper_generated::__tauri_collected_handler());
+        assert!(builder.invoke("__tauri_helper_ping"));
+        assert!(builder.invoke("sum"));
+        assert!(
+            !tauri::Builder::default()
+                .invoke_handler(collected_handler())
+                .invoke("__tauri_helper_ping")
+        );
+        // the version of the macros
+        let version = pinged::__tauri_helper_generated::__tauri_helper_ping();
+        assert_eq!(version.split('.').count(), 3);
+    }
+
+    #[test]
+    fn feature_gated_commands_follow_the_features() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert_eq!(builder.invoke("export_logs"), cfg!(feature = "admin"));
+        // only a command when the feature is enabled, through `cfg_attr`
+        assert_eq!(builder.invoke("purge_cache"), cfg!(feature = "admin"));
+    }
+
+    #[test]
+    fn commands_excluded_from_tests_are_not_registered_in_tests() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(!builder.invoke("quit"));
+        assert!(
+            registry::COMMANDS
+                .iter()
+                .all(|command| command.name != "quit")
+        );
+    }
+
+    #[test]
+    fn metadata_only_commands_are_not_registered() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(!builder.invoke("describe"));
+        // they are still described by the metadata outputs
+        assert!(
+            registry::COMMANDS
+                .iter()
+                .any(|command| command.path == "docs::describe")
+        );
+    }
+
+    #[test]
+    fn registry_lists_the_metadata_of_every_command() {
+        let names: Vec<&str> = registry::COMMANDS
+            .iter()
+            .map(|command| command.name)
+            .collect();
+        let mut expected = vec![
+            "describe",
+            "greet",
+            "greet",
+            "load_user",
+            "open_window",
+            "scan_barcode",
+            "sum",
+            "teleport",
+        ];
+        // the entries of feature-gated commands are gated as well
+        if cfg!(feature = "admin") {
+            expected.insert(1, "export_logs");
+            expected.insert(6, "purge_cache");
+        }
+        assert_eq!(names, expected);
+
+        let sum = registry::COMMANDS
+            .iter()
+            .find(|command| command.name == "sum")
+            .unwrap();
+        assert_eq!(sum.path, "app::commands::add");
+        assert_eq!(sum.group, "");
+    }
+
+    #[test]
+    fn generated_builder_registers_the_commands() {
+        let builder = tauri_builder();
+        assert!(builder.invoke("load_user"));
+        assert!(builder.invoke("sum"));
+        assert!(!builder.invoke("describe"));
+    }
+
+    #[test]
+    fn command_counts_are_set_by_the_build_script() {
+        // gated commands are counted whatever the features
+        assert_eq!(env!("TAURI_HELPER_COMMAND_COUNT_APP"), "9");
+        assert_eq!(env!("TAURI_HELPER_COMMAND_COUNT_PLUGIN"), "1");
+        assert_eq!(env!("TAURI_HELPER_COMMAND_COUNT_DOCS"), "1");
+        assert_eq!(env!("TAURI_HELPER_COMMAND_COUNT_TAURI_MACROS"), "0");
+    }
+
+    #[test]
+    fn commands_are_called_through_their_wrapper() {
+        assert_eq!(invoke::app_greet("Alice".to_string()), "Hello, Alice!");
+        assert_eq!(invoke::sum(2, 3), 5);
+        assert_eq!(ready(invoke::load_user(7)), Ok(commands::User { id: 7 }));
+    }
+
+    #[test]
+    fn commands_of_raw_identifier_modules_are_registered() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(builder.invoke("teleport"));
+        assert_eq!(invoke::teleport(1, 2), (1, 2));
+    }
+
+    #[test]
+    fn clashing_names_are_prefixed_with_their_crate() {
+        assert_eq!(invoke::plugin_greet(), "Hello from the plugin!");
+    }
+}
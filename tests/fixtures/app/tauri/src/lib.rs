@@ -0,0 +1,92 @@
+//! The parts of the Tauri API the expansions of the tauri-helper macros use, registering commands
+//! by name so that the fixtures can check which commands a handler answers.
+
+pub use tauri_macros::command;
+
+/// The default runtime.
+pub struct Wry;
+
+pub mod ipc {
+    /// The message of an invocation, only holding the name of the command.
+    pub struct InvokeMessage {
+        command: String,
+    }
+
+    impl InvokeMessage {
+        pub fn command(&self) -> &str {
+            &self.command
+        }
+    }
+
+    pub struct Invoke<R = super::Wry> {
+        pub message: InvokeMessage,
+        runtime: std::marker::PhantomData<R>,
+    }
+
+    impl Invoke {
+        /// Invokes `command`.
+        pub fn new(command: &str) -> Self {
+            Self {
+                message: InvokeMessage {
+                    command: command.to_string(),
+                },
+                runtime: std::marker::PhantomData,
+            }
+        }
+    }
+}
+
+pub type InvokeHandler = Box<dyn Fn(ipc::Invoke) -> bool + Send + Sync>;
+
+/// Builds a handler answering the commands named after the last segment of the given paths, which
+/// must resolve to functions.
+#[macro_export]
+macro_rules! generate_handler {
+    ($($(#[$meta:meta])* $path:path),* $(,)?) => {{
+        let handler: $crate::InvokeHandler = Box::new(move |invoke: $crate::ipc::Invoke| {
+            let command = invoke.message.command();
+            $(
+                $(#[$meta])*
+                {
+                    let _ = $path;
+                    if stringify!($path).rsplit(' ').next() == Some(command) {
+                        return true;
+                    }
+                }
+            )*
+            false
+        });
+        handler
+    }};
+}
+
+pub struct Builder<R> {
+    handler: Option<InvokeHandler>,
+    runtime: std::marker::PhantomData<R>,
+}
+
+impl Default for Builder<Wry> {
+    fn default() -> Self {
+        Self {
+            handler: None,
+            runtime: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<R> Builder<R> {
+    pub fn invoke_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(ipc::Invoke) -> bool + Send + Sync + 'static,
+    {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Returns whether the handler answers `command`.
+    pub fn invoke(&self, command: &str) -> bool {
+        self.handler
+            .as_ref()
+            .is_some_and(|handler| handler(ipc::Invoke::new(command)))
+    }
+}
@@ -0,0 +1,26 @@
+use proc_macro::{TokenStream, TokenTree};
+
+/// Returns the function untouched along with the `__cmd__<name>` macro Tauri defines next to
+/// each command, which renamed commands are re-exported with.
+#[proc_macro_attribute]
+pub fn command(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut tokens = item.clone().into_iter();
+    let name = loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "fn" => match tokens.next() {
+                Some(TokenTree::Ident(name)) => break name.to_string(),
+                _ => panic!("expected the name of the function"),
+            },
+            Some(_) => {}
+            None => panic!("`#[command]` must be put on a function"),
+        }
+    };
+    let name = name.trim_start_matches("r#");
+    let wrapper: TokenStream = format!(
+        "#[doc(hidden)] #[allow(unused_macros)] macro_rules! __cmd__{name} {{ () => {{}} }} \
+        #[doc(hidden)] #[allow(unused_imports)] pub(crate) use __cmd__{name};"
+    )
+    .parse()
+    .unwrap();
+    item.into_iter().chain(wrapper).collect()
+}
@@ -0,0 +1,19 @@
+//! An app whose command files are committed rather than written by a build script, built by the
+//! tests of tauri-helper.
+
+pub mod users;
+
+#[tauri::command]
+pub fn greet() {}
+
+#[cfg(test)]
+mod tests {
+    use tauri_helper::tauri_collect_commands;
+
+    #[test]
+    fn commands_are_read_from_the_committed_directory() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(builder.invoke("greet"));
+        assert!(builder.invoke("load_user"));
+    }
+}
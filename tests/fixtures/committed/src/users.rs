@@ -0,0 +1,2 @@
+#[tauri::command]
+pub fn load_user() {}
@@ -0,0 +1,3 @@
+fn main() {
+    tauri_helper::generate_command_file(tauri_helper::TauriHelperOptions::default());
+}
@@ -0,0 +1,23 @@
+//! A single-crate app, with no workspace, built by the tests of tauri-helper. They copy it out of
+//! this repository, whose workspace would be found above it, and write its manifest.
+
+use tauri_helper::auto_collect_command;
+
+pub mod settings;
+
+#[tauri::command]
+#[auto_collect_command]
+pub fn greet() {}
+
+#[cfg(test)]
+mod tests {
+    use tauri_helper::tauri_collect_commands;
+
+    #[test]
+    fn commands_of_the_only_crate_are_collected() {
+        let builder = tauri::Builder::default().invoke_handler(tauri_collect_commands!());
+        assert!(builder.invoke("greet"));
+        assert!(builder.invoke("set_theme"));
+        assert!(!builder.invoke("reset"));
+    }
+}
@@ -0,0 +1,10 @@
+use tauri_helper::auto_collect_command;
+
+#[tauri::command]
+#[auto_collect_command]
+pub fn set_theme(theme: String) -> String {
+    theme
+}
+
+#[tauri::command]
+pub fn reset() {}